@@ -0,0 +1,160 @@
+/// Generates the solution registry used by the `registry` feature.
+/// Every module in `src/bin` is compiled into the main binary and its `SOLUTION` constant is collected.
+/// Also exposes the compiler version as `AOC_RUSTC_VERSION`, which is recorded in the timing history,
+/// and generates a test per example and part of every example manifest, which the `solution!` macro includes.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[path = "src/template/examples/manifest.rs"]
+#[allow(dead_code)]
+mod manifest;
+
+/// Mirrors the fallback of `Year::default`.
+const FALLBACK_YEAR: &str = "2024";
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    if let Some(version) = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+    {
+        println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", version.trim());
+    }
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut modules: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let name = format!(
+                        "solution_{}",
+                        stem.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+                    );
+                    Some((name, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    modules.sort();
+
+    let mut generated = String::new();
+
+    for (name, path) in &modules {
+        generated.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code, unused_imports)]\nmod {name};\n"
+        ));
+    }
+
+    generated
+        .push_str("\npub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (name, _) in &modules {
+        generated.push_str(&format!("    {name}::SOLUTION,\n"));
+    }
+    generated.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), generated).unwrap();
+
+    generate_example_tests(&bin_dir, &out_dir.join("examples"));
+}
+
+/// Writes `<bin name>.rs` to the output directory for every solution binary.
+fn generate_example_tests(bin_dir: &Path, out_dir: &Path) {
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+    let default_year = env::var("AOC_YEAR").unwrap_or_else(|_| FALLBACK_YEAR.into());
+
+    fs::create_dir_all(out_dir).unwrap();
+
+    // solution modules compiled into the main binary by the `registry` feature include the file of the main binary.
+    fs::write(
+        out_dir.join(format!("{}.rs", env::var("CARGO_PKG_NAME").unwrap())),
+        "",
+    )
+    .unwrap();
+
+    let stems: Vec<String> = fs::read_dir(bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for stem in stems {
+        let path = manifest_path(&stem, &default_year);
+
+        if let Some(dir) = path.parent().filter(|dir| dir.is_dir()) {
+            println!("cargo:rerun-if-changed={}", dir.display());
+        }
+
+        let tests = match fs::read_to_string(&path) {
+            Ok(json) => example_tests(&path, &json),
+            Err(_) => String::new(),
+        };
+
+        fs::write(out_dir.join(format!("{stem}.rs")), tests).unwrap();
+    }
+}
+
+/// Mirrors `Puzzle::data_path` for the binary names of `Puzzle::bin_name`, e.g. `05` or `2023_05`.
+fn manifest_path(bin_name: &str, default_year: &str) -> PathBuf {
+    let (year, day) = bin_name.split_once('_').unwrap_or((default_year, bin_name));
+
+    let data_dir = Path::new("data");
+    let year_dir = data_dir.join(year);
+    let dir = if year != default_year || year_dir.is_dir() {
+        year_dir
+    } else {
+        data_dir.to_path_buf()
+    };
+
+    dir.join("examples").join(format!("{day}.json"))
+}
+
+fn example_tests(path: &Path, json: &str) -> String {
+    let examples = match manifest::parse_manifest(json) {
+        Ok(examples) => examples,
+        // surface invalid manifests as a failing test instead of breaking the build.
+        Err(e) => {
+            let message = format!("invalid example manifest \"{}\": {e}", path.display());
+            return format!(
+                "#[test]\nfn example_manifest() {{\n    panic!(\"{{}}\", {message:?});\n}}\n"
+            );
+        }
+    };
+
+    let mut tests = String::new();
+
+    for example in &examples {
+        let name = example
+            .file
+            .trim_end_matches(".txt")
+            .to_lowercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+        for (part, expected) in &example.parts {
+            tests.push_str(&format!(
+                "#[test]\nfn example_{name}_part_{part}() {{\n    \
+                advent_of_code::template::examples::check(&super::SOLUTION, {:?}, {part}, {expected:?});\n}}\n\n",
+                example.file
+            ));
+        }
+    }
+
+    tests
+}
@@ -1,58 +1,46 @@
 use std::{collections::HashMap, iter::zip};
 
+use advent_of_code::template::{parse_lines, ParseError, Solution};
 use nom::{
-    character::complete::{line_ending, space1, u32},
-    combinator::opt,
-    multi::fold_many0,
-    sequence::{separated_pair, terminated},
-    IResult,
+    character::complete::{space1, u32},
+    sequence::separated_pair,
 };
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, Day01);
 
-fn parse_part_one(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
-    fold_many0(
-        terminated(separated_pair(u32, space1, u32), opt(line_ending)),
-        || (Vec::new(), Vec::new()),
-        |mut acc: (Vec<u32>, Vec<u32>), item: (u32, u32)| {
-            acc.0.push(item.0);
-            acc.1.push(item.1);
-            acc
-        },
-    )(input)
-}
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// The left and the right list.
+    type Parsed = (Vec<u32>, Vec<u32>);
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-pub fn part_one(input: &str) -> Option<String> {
-    parse_part_one(input).ok().map(|(_, (mut v1, mut v2))| {
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        parse_lines(input, separated_pair(u32, space1, u32)).map(|pairs| pairs.into_iter().unzip())
+    }
+
+    fn part_one((v1, v2): &Self::Parsed) -> Option<Self::Answer1> {
+        let (mut v1, mut v2) = (v1.clone(), v2.clone());
         v1.sort();
         v2.sort();
-        zip(v1, v2)
-            .map(|(x, y)| x.abs_diff(y) as u64)
-            .sum::<u64>()
-            .to_string()
-    })
-}
-
-fn parse_part_two(input: &str) -> IResult<&str, (Vec<u32>, HashMap<u32, u32>)> {
-    fold_many0(
-        terminated(separated_pair(u32, space1, u32), opt(line_ending)),
-        || (Vec::new(), HashMap::new()),
-        |mut acc: (Vec<u32>, HashMap<u32, u32>), item: (u32, u32)| {
-            acc.0.push(item.0);
-            *acc.1.entry(item.1).or_insert(0) += 1;
-            acc
-        },
-    )(input)
-}
+        Some(zip(v1, v2).map(|(x, y)| x.abs_diff(y) as u64).sum())
+    }
 
-pub fn part_two(input: &str) -> Option<String> {
-    parse_part_two(input).ok().map(|(_, (v1, v2))| {
-        v1.iter()
-            .map(|x| x * *v2.get(x).unwrap_or(&0u32))
-            .map(|x| x as u64)
-            .sum::<u64>()
-            .to_string()
-    })
+    fn part_two((v1, v2): &Self::Parsed) -> Option<Self::Answer2> {
+        let mut counts: HashMap<u32, u32> = HashMap::new();
+        for x in v2 {
+            *counts.entry(*x).or_insert(0) += 1;
+        }
+
+        Some(
+            v1.iter()
+                .map(|x| x * *counts.get(x).unwrap_or(&0u32))
+                .map(|x| x as u64)
+                .sum(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -61,13 +49,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("11".to_string()));
+        let input = Day01::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day01::part_one(&input.unwrap());
+        assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("31".to_string()));
+        let input = Day01::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day01::part_two(&input.unwrap());
+        assert_eq!(result, Some(31));
     }
 }
@@ -1,12 +1,10 @@
+use advent_of_code::template::{parse_lines, ParseError, Solution};
 use nom::{
-    character::{
-        complete::u32,
-        complete::{line_ending, space1},
-    },
+    character::{complete::space1, complete::u32},
     multi::separated_list1,
 };
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, Day02);
 
 fn check_row(row: &[u32]) -> bool {
     let order = row.first().cmp(&row.get(1));
@@ -15,48 +13,49 @@ fn check_row(row: &[u32]) -> bool {
         .all(|(x, y)| x.cmp(&y) == order && x.abs_diff(y) <= 3)
 }
 
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    let new_line = line_ending::<&str, ()>;
-    let result = separated_list1(new_line, separated_list1(space1, u32))(input);
-    result.expect("Correct input format").1
-}
-
-pub fn part_one(input: &str) -> Option<String> {
-    let data = parse_input(input);
+pub struct Day02;
 
-    let total = data.iter().filter(|row| check_row(row)).count();
+impl Solution for Day02 {
+    type Parsed = Vec<Vec<u32>>;
+    type Error = ParseError;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Some(total.to_string())
-}
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        parse_lines(input, separated_list1(space1, u32))
+    }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let data = parse_input(input);
+    fn part_one(data: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(data.iter().filter(|row| check_row(row)).count())
+    }
 
-    let total = data
-        .iter()
-        .filter(|row| {
-            if check_row(row) {
-                return true;
-            }
+    fn part_two(data: &Self::Parsed) -> Option<Self::Answer2> {
+        let total = data
+            .iter()
+            .filter(|row| {
+                if check_row(row) {
+                    return true;
+                }
 
-            for n in 0..row.len() {
-                let new_row = row
-                    .iter()
-                    .enumerate()
-                    .filter(|&(i, _)| i != n)
-                    .map(|(_, v)| *v)
-                    .collect::<Vec<u32>>();
+                for n in 0..row.len() {
+                    let new_row = row
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, _)| i != n)
+                        .map(|(_, v)| *v)
+                        .collect::<Vec<u32>>();
 
-                if check_row(&new_row) {
-                    return true;
+                    if check_row(&new_row) {
+                        return true;
+                    }
                 }
-            }
 
-            false
-        })
-        .count();
+                false
+            })
+            .count();
 
-    Some(total.to_string())
+        Some(total)
+    }
 }
 
 #[cfg(test)]
@@ -65,13 +64,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("2".to_string()));
+        let input = Day02::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day02::part_one(&input.unwrap());
+        assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("4".to_string()));
+        let input = Day02::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day02::part_two(&input.unwrap());
+        assert_eq!(result, Some(4));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::template::{parse_all, ParseError, Solution};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take, take_while_m_n},
@@ -7,7 +8,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, Day03);
 
 fn find_num3(input: &str) -> IResult<&str, u32> {
     map_res(take_while_m_n(1, 3, char::is_numeric), |s: &str| {
@@ -23,22 +24,8 @@ fn parse_mul(input: &str) -> IResult<&str, (u32, u32)> {
     )(input)
 }
 
-fn parse_part_one(input: &str) -> Vec<(u32, u32)> {
-    let x = many1(alt((parse_mul, value((0, 0), take(1u32)))))(input);
-    x.expect("Correct input format").1
-}
-
-pub fn part_one(input: &str) -> Option<String> {
-    let data = parse_part_one(input);
-    Some(
-        (data
-            .iter()
-            .filter(|(x, y)| *x < 1000 && *y < 1000)
-            .map(|(x, y)| *x * *y)
-            .map(|x| x as u64)
-            .sum::<u64>())
-        .to_string(),
-    )
+fn parse_part_one(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
+    many1(alt((parse_mul, value((0, 0), take(1u32)))))(input)
 }
 
 struct State {
@@ -55,8 +42,8 @@ impl State {
     }
 }
 
-fn parse_part_two(input: &str) -> State {
-    let x = fold_many1(
+fn parse_part_two(input: &str) -> IResult<&str, State> {
+    fold_many1(
         alt((
             value((Some(true), (0, 0)), tag("do()")),
             value((Some(false), (0, 0)), tag("don't()")),
@@ -73,22 +60,46 @@ fn parse_part_two(input: &str) -> State {
             }
             acc
         },
-    )(input);
+    )(input)
+}
 
-    x.expect("Correct input format").1
+fn sum_products(pairs: &[(u32, u32)]) -> u64 {
+    pairs
+        .iter()
+        .filter(|(x, y)| *x < 1000 && *y < 1000)
+        .map(|(x, y)| *x * *y)
+        .map(|x| x as u64)
+        .sum()
+}
+
+/// The multiplications of the program, and those of them that are enabled by `do()` and `don't()`.
+pub struct Program {
+    pairs: Vec<(u32, u32)>,
+    enabled: Vec<(u32, u32)>,
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let data = parse_part_two(input);
-    Some(
-        data.pairs
-            .iter()
-            .filter(|(x, y)| *x < 1000 && *y < 1000)
-            .map(|(x, y)| *x * *y)
-            .map(|x| x as u64)
-            .sum::<u64>()
-            .to_string(),
-    )
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Program;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Ok(Program {
+            pairs: parse_all(input, parse_part_one)?,
+            enabled: parse_all(input, parse_part_two)?.pairs,
+        })
+    }
+
+    fn part_one(program: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(sum_products(&program.pairs))
+    }
+
+    fn part_two(program: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(sum_products(&program.enabled))
+    }
 }
 
 #[cfg(test)]
@@ -97,13 +108,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("161".to_string()));
+        let input = Day03::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day03::part_one(&input.unwrap());
+        assert_eq!(result, Some(161));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("48".to_string()));
+        let input = Day03::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day03::part_two(&input.unwrap());
+        assert_eq!(result, Some(48));
     }
 }
@@ -1,121 +1,72 @@
-use advent_of_code::{Point, ALL_8_POINTS};
-use nom::{
-    character::complete::{alpha1, line_ending},
-    multi::separated_list1,
-};
-
-advent_of_code::solution!(4);
-
-fn search_from(start: Point, rows: &[&str]) -> u64 {
-    let mut acc = 0;
-    for direction in *ALL_8_POINTS {
-        let mut pointer = start;
-        let mut found = true;
-        for expected in "MAS".chars() {
-            pointer += direction;
-            if pointer.0 < 0 || pointer.0 as usize >= rows.len() {
-                found = false;
-                break;
-            }
-
-            if let Some(&row) = rows.get(pointer.0 as usize) {
-                if pointer.1 < 0 || pointer.1 as usize >= row.len() {
-                    found = false;
-                    break;
-                }
-                if let Some(chr) = row.chars().nth(pointer.1 as usize) {
-                    if chr != expected {
-                        found = false;
-                        break;
-                    }
-                }
-            }
-        }
-        if found {
-            acc += 1;
-        }
-    }
-    acc
+use advent_of_code::template::{ParseError, Solution};
+use advent_of_code::{Matrix, MatrixView, Point};
+
+advent_of_code::solution!(4, Day04);
+
+/// Occurrences of `XMAS` in a line of the word search, read in both directions.
+fn count_in_line<'a>(line: impl Iterator<Item = &'a u8>) -> u64 {
+    let line: Vec<u8> = line.copied().collect();
+    line.windows(4)
+        .filter(|word| *word == b"XMAS" || *word == b"SAMX")
+        .count() as u64
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let result = separated_list1(new_line, alpha1)(input);
-    let rows: Vec<&str> = result.expect("Correct input format").1;
+fn count_xmas(matrix: &Matrix<u8>) -> u64 {
+    let rows = matrix.iter_rows().map(|row| count_in_line(row.iter()));
+    let columns = matrix.iter_columns().map(count_in_line);
+    let diagonals = matrix.diagonals().map(count_in_line);
+    let anti_diagonals = matrix.anti_diagonals().map(count_in_line);
 
-    let mut starts = Vec::new();
+    rows.chain(columns)
+        .chain(diagonals)
+        .chain(anti_diagonals)
+        .sum()
+}
 
-    for (r, row) in rows.iter().enumerate() {
-        for (c, chr) in row.chars().enumerate() {
-            if chr == 'X' {
-                starts.push(Point(r as isize, c as isize));
-            }
-        }
-    }
+fn is_x_mas(view: &MatrixView<u8>) -> bool {
+    let chr = |row, col| *view.get(&Point(row, col)).expect("Checked");
 
-    let mut acc = 0;
-    for start in starts {
-        acc += search_from(start, &rows);
+    if chr(1, 1) != b'A' {
+        return false;
     }
 
-    Some(acc.to_string())
+    matches!(
+        (chr(0, 0), chr(0, 2), chr(2, 0), chr(2, 2)),
+        (b'M', b'M', b'S', b'S')
+            | (b'S', b'M', b'S', b'M')
+            | (b'S', b'S', b'M', b'M')
+            | (b'M', b'S', b'M', b'S')
+    )
 }
 
-fn search_cross(start: (i32, i32), rows: &[&str]) -> u64 {
-    let a = rows
-        .get(start.0 as usize - 1)
-        .unwrap()
-        .chars()
-        .nth(start.1 as usize - 1)
-        .unwrap();
-    let b = rows
-        .get(start.0 as usize - 1)
-        .unwrap()
-        .chars()
-        .nth(start.1 as usize + 1)
-        .unwrap();
-    let c = rows
-        .get(start.0 as usize + 1)
-        .unwrap()
-        .chars()
-        .nth(start.1 as usize - 1)
-        .unwrap();
-    let d = rows
-        .get(start.0 as usize + 1)
-        .unwrap()
-        .chars()
-        .nth(start.1 as usize + 1)
-        .unwrap();
-    match (a, b, c, d) {
-        ('M', 'M', 'S', 'S')
-        | ('S', 'M', 'S', 'M')
-        | ('S', 'S', 'M', 'M')
-        | ('M', 'S', 'M', 'S') => 1,
-        _ => 0,
-    }
+fn count_x_mas(matrix: &Matrix<u8>) -> u64 {
+    matrix
+        .as_points()
+        .filter_map(|p| matrix.view(&p, 3, 3))
+        .filter(is_x_mas)
+        .count() as u64
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let result = separated_list1(new_line, alpha1)(input);
-    let rows: Vec<&str> = result.expect("Correct input format").1;
+pub struct Day04;
 
-    let mut starts = Vec::new();
+impl Solution for Day04 {
+    /// The letters of the word search.
+    type Parsed = Matrix<u8>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    for (r, row) in rows.iter().enumerate() {
-        for (c, chr) in row.chars().enumerate() {
-            if r != 0 && r != rows.len() - 1 && c != 0 && c != row.len() - 1 && chr == 'A' {
-                starts.push((r as i32, c as i32));
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Matrix::parse(input)
     }
 
-    let mut acc = 0;
-    for start in starts {
-        acc += search_cross(start, &rows);
+    fn part_one(matrix: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(count_xmas(matrix))
     }
 
-    Some(acc.to_string())
+    fn part_two(matrix: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(count_x_mas(matrix))
+    }
 }
 
 #[cfg(test)]
@@ -124,13 +75,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("18".to_string()));
+        let input = Day04::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day04::part_one(&input.unwrap());
+        assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("9".to_string()));
+        let input = Day04::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day04::part_two(&input.unwrap());
+        assert_eq!(result, Some(9));
     }
 }
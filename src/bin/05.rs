@@ -1,3 +1,4 @@
+use advent_of_code::template::{parse_all, ParseError, Solution};
 use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, u32},
@@ -5,17 +6,10 @@ use nom::{
     sequence::separated_pair,
 };
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, Day05);
 
-fn parse_input(input: &str) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
-    let new_line = line_ending::<&str, ()>;
-    let result = separated_pair(
-        separated_list1(new_line, separated_pair(u32, tag("|"), u32)),
-        tag("\n\n"),
-        separated_list1(new_line, separated_list1(tag(","), u32)),
-    )(input);
-    result.expect("Correct input format").1
-}
+/// The page ordering rules and the updates.
+type Manual = (Vec<(u32, u32)>, Vec<Vec<u32>>);
 
 fn find_rule(rules: &Vec<(u32, u32)>, pair: &[u32]) -> bool {
     for rule in rules {
@@ -27,19 +21,6 @@ fn find_rule(rules: &Vec<(u32, u32)>, pair: &[u32]) -> bool {
     false
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let (rules, updates) = parse_input(input);
-    let mut acc = 0;
-    for update in &updates {
-        if update.windows(2).all(|pair| find_rule(&rules, pair)) {
-            let mid = update.len().div_euclid(2);
-            acc += update.get(mid).unwrap();
-        }
-    }
-
-    Some(acc.to_string())
-}
-
 fn sort_by_rules(mut update: Vec<u32>, rules: &Vec<(u32, u32)>) -> Vec<u32> {
     loop {
         let mut has_updated = false;
@@ -56,20 +37,50 @@ fn sort_by_rules(mut update: Vec<u32>, rules: &Vec<(u32, u32)>) -> Vec<u32> {
     update
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (rules, updates) = parse_input(input);
+pub struct Day05;
 
-    let mut acc = 0;
-    for mut update in updates {
-        if update.windows(2).all(|pair| find_rule(&rules, pair)) {
-            continue;
+impl Solution for Day05 {
+    type Parsed = Manual;
+    type Error = ParseError;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        parse_all(
+            input,
+            separated_pair(
+                separated_list1(line_ending, separated_pair(u32, tag("|"), u32)),
+                tag("\n\n"),
+                separated_list1(line_ending, separated_list1(tag(","), u32)),
+            ),
+        )
+    }
+
+    fn part_one((rules, updates): &Self::Parsed) -> Option<Self::Answer1> {
+        let mut acc = 0;
+        for update in updates {
+            if update.windows(2).all(|pair| find_rule(rules, pair)) {
+                let mid = update.len().div_euclid(2);
+                acc += update.get(mid).unwrap();
+            }
         }
-        update = sort_by_rules(update, &rules);
-        let mid = update.len().div_euclid(2);
-        acc += update.get(mid).unwrap();
+
+        Some(acc)
     }
 
-    Some(acc.to_string())
+    fn part_two((rules, updates): &Self::Parsed) -> Option<Self::Answer2> {
+        let mut acc = 0;
+        for update in updates {
+            if update.windows(2).all(|pair| find_rule(rules, pair)) {
+                continue;
+            }
+            let update = sort_by_rules(update.clone(), rules);
+            let mid = update.len().div_euclid(2);
+            acc += update.get(mid).unwrap();
+        }
+
+        Some(acc)
+    }
 }
 
 #[cfg(test)]
@@ -78,13 +89,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("143".to_string()));
+        let input = Day05::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day05::part_one(&input.unwrap());
+        assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("123".to_string()));
+        let input = Day05::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day05::part_two(&input.unwrap());
+        assert_eq!(result, Some(123));
     }
 }
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
+use advent_of_code::template::{ParseError, Solution};
 use advent_of_code::{Matrix, Point};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, Day06);
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
@@ -86,10 +87,8 @@ fn search_guard(matrix: &Matrix<u8>) -> Option<Guard> {
     None
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-
-    let mut matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
+fn count_visited(matrix: &Matrix<u8>) -> u64 {
+    let mut matrix = matrix.clone();
 
     let mut guard = search_guard(&matrix).expect("At least one guard");
 
@@ -102,7 +101,7 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
-    Some(acc.to_string())
+    acc
 }
 
 fn search_loop(guard: Guard, mut matrix: Matrix<u8>) -> Option<bool> {
@@ -119,12 +118,8 @@ fn search_loop(guard: Guard, mut matrix: Matrix<u8>) -> Option<bool> {
     }
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
-
-    let guard = search_guard(&matrix).expect("At least one guard");
+fn count_loop_obstructions(matrix: &Matrix<u8>) -> u64 {
+    let guard = search_guard(matrix).expect("At least one guard");
 
     let mut possible_positions = HashSet::with_capacity((matrix.rows * matrix.cols) as usize);
 
@@ -146,7 +141,28 @@ pub fn part_two(input: &str) -> Option<String> {
         }
     }
 
-    Some(acc.to_string())
+    acc
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Matrix<u8>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Matrix::parse(input)
+    }
+
+    fn part_one(matrix: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(count_visited(matrix))
+    }
+
+    fn part_two(matrix: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(count_loop_obstructions(matrix))
+    }
 }
 
 #[cfg(test)]
@@ -155,13 +171,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("41".to_string()));
+        let input = Day06::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day06::part_one(&input.unwrap());
+        assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6".to_string()));
+        let input = Day06::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day06::part_two(&input.unwrap());
+        assert_eq!(result, Some(6));
     }
 }
@@ -1,13 +1,17 @@
 use std::collections::VecDeque;
 
+use advent_of_code::template::{parse_lines, ParseError, Solution};
 use nom::{
     bytes::complete::tag,
-    character::complete::{line_ending, space1, u64},
+    character::complete::{space1, u64},
     multi::separated_list1,
     sequence::separated_pair,
 };
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, Day07);
+
+/// A test value and the numbers to combine into it.
+type Equation = (u64, Vec<u64>);
 
 fn is_possible(total: u64, factors: &mut VecDeque<u64>) -> bool {
     if factors.is_empty() {
@@ -27,31 +31,13 @@ fn is_possible(total: u64, factors: &mut VecDeque<u64>) -> bool {
         return true;
     }
 
-    if total % first == 0 && is_possible(total / first, &mut factors.clone()) {
+    if total.is_multiple_of(first) && is_possible(total / first, &mut factors.clone()) {
         return true;
     }
 
     false
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let data = separated_list1(
-        new_line,
-        separated_pair(u64, tag(": "), separated_list1(space1, u64)),
-    )(input.trim())
-    .expect("Correct input format")
-    .1;
-
-    let mut acc = 0u64;
-    for (total, factors) in data {
-        if is_possible(total, &mut VecDeque::from(factors)) {
-            acc += total;
-        }
-    }
-    Some(acc.to_string())
-}
-
 fn is_possible_part_two(total: u64, factors: &mut VecDeque<u64>) -> bool {
     if factors.is_empty() {
         return false;
@@ -70,7 +56,7 @@ fn is_possible_part_two(total: u64, factors: &mut VecDeque<u64>) -> bool {
         return true;
     }
 
-    if total % first == 0 && is_possible_part_two(total / first, &mut factors.clone()) {
+    if total.is_multiple_of(first) && is_possible_part_two(total / first, &mut factors.clone()) {
         return true;
     }
 
@@ -86,22 +72,40 @@ fn is_possible_part_two(total: u64, factors: &mut VecDeque<u64>) -> bool {
     false
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let data = separated_list1(
-        new_line,
-        separated_pair(u64, tag(": "), separated_list1(space1, u64)),
-    )(input.trim())
-    .expect("Correct input format")
-    .1;
-
-    let mut acc = 0u64;
-    for (total, factors) in data {
-        if is_possible_part_two(total, &mut VecDeque::from(factors)) {
-            acc += total;
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<Equation>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        parse_lines(
+            input,
+            separated_pair(u64, tag(": "), separated_list1(space1, u64)),
+        )
+    }
+
+    fn part_one(data: &Self::Parsed) -> Option<Self::Answer1> {
+        let mut acc = 0u64;
+        for (total, factors) in data {
+            if is_possible(*total, &mut VecDeque::from(factors.clone())) {
+                acc += total;
+            }
+        }
+        Some(acc)
+    }
+
+    fn part_two(data: &Self::Parsed) -> Option<Self::Answer2> {
+        let mut acc = 0u64;
+        for (total, factors) in data {
+            if is_possible_part_two(*total, &mut VecDeque::from(factors.clone())) {
+                acc += total;
+            }
         }
+        Some(acc)
     }
-    Some(acc.to_string())
 }
 
 #[cfg(test)]
@@ -110,13 +114,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("3749".to_string()));
+        let input = Day07::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day07::part_one(&input.unwrap());
+        assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("11387".to_string()));
+        let input = Day07::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day07::part_two(&input.unwrap());
+        assert_eq!(result, Some(11387));
     }
 }
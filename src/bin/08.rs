@@ -1,14 +1,13 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use advent_of_code::template::{ParseError, Solution};
 use advent_of_code::Matrix;
 use advent_of_code::Point;
-advent_of_code::solution!(8);
-
-pub fn part_one(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
+advent_of_code::solution!(8, Day08);
 
+/// Group the antennas by their frequency.
+fn antennas(matrix: &Matrix<u8>) -> HashMap<u8, Vec<Point>> {
     let mut letters: HashMap<u8, Vec<Point>> = HashMap::new();
     for p in matrix.as_points() {
         let chr = matrix.get(&p).expect("Checked");
@@ -17,8 +16,12 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
+    letters
+}
+
+fn count_antinodes(matrix: &Matrix<u8>) -> usize {
     let mut result = HashSet::new();
-    for (_chr, letter) in letters.into_iter() {
+    for (_chr, letter) in antennas(matrix).into_iter() {
         for p1 in &letter {
             for p2 in &letter {
                 let new_point = Point(
@@ -33,23 +36,12 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
-    Some(result.len().to_string())
+    result.len()
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
-
-    let mut letters: HashMap<u8, Vec<Point>> = HashMap::new();
-    for p in matrix.as_points() {
-        let chr = matrix.get(&p).expect("Checked");
-        if *chr != b'.' {
-            letters.entry(*chr).or_default().push(p);
-        }
-    }
-
+fn count_resonant_antinodes(matrix: &Matrix<u8>) -> usize {
     let mut result = HashSet::new();
-    for (_chr, letter) in letters.into_iter() {
+    for (_chr, letter) in antennas(matrix).into_iter() {
         for p1 in &letter {
             for p2 in &letter {
                 if p1 == p2 {
@@ -66,7 +58,28 @@ pub fn part_two(input: &str) -> Option<String> {
         }
     }
 
-    Some(result.len().to_string())
+    result.len()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Matrix<u8>;
+    type Error = ParseError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Matrix::parse(input)
+    }
+
+    fn part_one(matrix: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(count_antinodes(matrix))
+    }
+
+    fn part_two(matrix: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(count_resonant_antinodes(matrix))
+    }
 }
 
 #[cfg(test)]
@@ -75,13 +88,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("14".to_string()));
+        let input = Day08::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day08::part_one(&input.unwrap());
+        assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("34".to_string()));
+        let input = Day08::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day08::part_two(&input.unwrap());
+        assert_eq!(result, Some(34));
     }
 }
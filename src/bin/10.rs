@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
-use advent_of_code::{Matrix, Point, ALL_4_DIRECTIONS};
+use advent_of_code::template::{ParseError, Solution};
+use advent_of_code::{Matrix, Point};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, Day10);
 
 fn dfs_part_one(position: Point, matrix: &Matrix<u8>) -> HashSet<Point> {
     let mut acc = HashSet::new();
@@ -12,24 +13,18 @@ fn dfs_part_one(position: Point, matrix: &Matrix<u8>) -> HashSet<Point> {
         acc.insert(position);
         return acc;
     }
-    for direction in ALL_4_DIRECTIONS {
-        let new_position = position + direction;
-        if let Some(p) = matrix.get(&new_position) {
-            if *p == *current_value + 1 {
-                let new_ends = dfs_part_one(new_position, matrix);
-                for end in new_ends {
-                    acc.insert(end);
-                }
+    for (new_position, p) in matrix.neighbors4(&position) {
+        if *p == *current_value + 1 {
+            let new_ends = dfs_part_one(new_position, matrix);
+            for end in new_ends {
+                acc.insert(end);
             }
         }
     }
 
     acc
 }
-pub fn part_one(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
-
+fn trailheads(matrix: &Matrix<u8>) -> Vec<Point> {
     let mut starts = Vec::new();
     for p in matrix.as_points() {
         let cell = matrix.get(&p).expect("Checked");
@@ -38,12 +33,16 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
+    starts
+}
+
+fn sum_scores(matrix: &Matrix<u8>) -> u64 {
     let mut acc = 0;
-    for start in starts {
-        acc += dfs_part_one(start, &matrix).len() as u64;
+    for start in trailheads(matrix) {
+        acc += dfs_part_one(start, matrix).len() as u64;
     }
 
-    Some(acc.to_string())
+    acc
 }
 
 fn dfs_part_two(position: Point, matrix: &Matrix<u8>) -> u64 {
@@ -53,36 +52,43 @@ fn dfs_part_two(position: Point, matrix: &Matrix<u8>) -> u64 {
     if *current_value == b'9' {
         return 1;
     }
-    for direction in ALL_4_DIRECTIONS {
-        let new_position = position + direction;
-        if let Some(p) = matrix.get(&new_position) {
-            if *p == *current_value + 1 {
-                acc += dfs_part_two(new_position, matrix);
-            }
+    for (new_position, p) in matrix.neighbors4(&position) {
+        if *p == *current_value + 1 {
+            acc += dfs_part_two(new_position, matrix);
         }
     }
 
     acc
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
+fn sum_ratings(matrix: &Matrix<u8>) -> u64 {
+    let mut acc = 0;
+    for start in trailheads(matrix) {
+        acc += dfs_part_two(start, matrix);
+    }
 
-    let mut starts = Vec::new();
-    for p in matrix.as_points() {
-        let cell = matrix.get(&p).expect("Checked");
-        if *cell == b'0' {
-            starts.push(p);
-        }
+    acc
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Matrix<u8>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Matrix::parse(input)
     }
 
-    let mut acc = 0;
-    for start in starts {
-        acc += dfs_part_two(start, &matrix);
+    fn part_one(matrix: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(sum_scores(matrix))
     }
 
-    Some(acc.to_string())
+    fn part_two(matrix: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(sum_ratings(matrix))
+    }
 }
 
 #[cfg(test)]
@@ -91,13 +97,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("36".to_string()));
+        let input = Day10::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day10::part_one(&input.unwrap());
+        assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("81".to_string()));
+        let input = Day10::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day10::part_two(&input.unwrap());
+        assert_eq!(result, Some(81));
     }
 }
@@ -1,21 +1,14 @@
 use std::collections::HashMap;
 
+use advent_of_code::template::{parse_all, ParseError, Solution};
 use nom::{character::complete::space1, character::complete::u32, multi::separated_list1};
 
-advent_of_code::solution!(11);
-
-pub fn part_one(input: &str) -> Option<String> {
-    let data = separated_list1(space1::<&str, ()>, u32)(input)
-        .expect("Correct input format")
-        .1;
-
-    Some(solve(25, data).to_string())
-}
+advent_of_code::solution!(11, Day11);
 
 fn next_num(x: u128) -> Vec<u128> {
     if x == 0 {
         vec![1]
-    } else if x.to_string().len() % 2 == 0 {
+    } else if x.to_string().len().is_multiple_of(2) {
         let z = x.to_string();
         let (a, b) = z.split_at(x.to_string().len() / 2);
         vec![a.parse::<u128>().unwrap(), b.parse::<u128>().unwrap()]
@@ -24,10 +17,10 @@ fn next_num(x: u128) -> Vec<u128> {
     }
 }
 
-fn solve(blinks: u32, data: Vec<u32>) -> u64 {
+fn solve(blinks: u32, data: &[u32]) -> u64 {
     let mut map: HashMap<u128, u64> = HashMap::new();
     for num in data {
-        *map.entry(num as u128).or_default() += 1;
+        *map.entry(*num as u128).or_default() += 1;
     }
 
     for _ in 0..blinks {
@@ -44,12 +37,26 @@ fn solve(blinks: u32, data: Vec<u32>) -> u64 {
     map.values().sum()
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let data = separated_list1(space1::<&str, ()>, u32)(input)
-        .expect("Correct input format")
-        .1;
+pub struct Day11;
 
-    Some(solve(75, data).to_string())
+impl Solution for Day11 {
+    /// The numbers engraved on the stones.
+    type Parsed = Vec<u32>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        parse_all(input, separated_list1(space1, u32))
+    }
+
+    fn part_one(data: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(solve(25, data))
+    }
+
+    fn part_two(data: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(solve(75, data))
+    }
 }
 
 #[cfg(test)]
@@ -58,13 +65,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("55312".to_string()));
+        let input = Day11::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day11::part_one(&input.unwrap());
+        assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("65601038650482".to_string()));
+        let input = Day11::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day11::part_two(&input.unwrap());
+        assert_eq!(result, Some(65601038650482));
     }
 }
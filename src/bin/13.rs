@@ -1,15 +1,17 @@
+use advent_of_code::template::fuzz::Rng;
+use advent_of_code::template::{parse_all, ParseError, Solution};
 use nom::{bytes::complete::tag, character::complete::u64, multi::separated_list1, IResult};
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, Day13, variants { 1 => fewest_tokens_solved }, generator = generate_games);
 
 #[derive(Debug)]
-struct Point {
+pub struct Point {
     x: u64,
     y: u64,
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     button_a: Point,
     button_b: Point,
     prize: Point,
@@ -55,17 +57,13 @@ fn get_game_info(game: &Game, pressed: (u64, u64)) -> Option<u64> {
     Some(pressed.0 * 3 + pressed.1)
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let data = separated_list1(tag("\n\n"), parse_part_one)(input.trim())
-        .expect("Correct input format")
-        .1;
-
+fn fewest_tokens(games: &[Game]) -> u64 {
     let mut total_tokens = 0;
-    for game in data {
+    for game in games {
         let mut min_tokens = None;
         for i in 0..=100 {
             for j in 0..=100 {
-                if let Some(tokens) = get_game_info(&game, (i, j)) {
+                if let Some(tokens) = get_game_info(game, (i, j)) {
                     if min_tokens.is_none() || min_tokens.is_some_and(|m| tokens < m) {
                         min_tokens = Some(tokens);
                     }
@@ -77,28 +75,19 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
-    Some(total_tokens.to_string())
+    total_tokens
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let mut data = separated_list1(tag("\n\n"), parse_part_one)(input.trim())
-        .expect("Correct input format")
-        .1;
-
-    data.iter_mut().for_each(|g| {
-        g.prize.x += 10000000000000;
-        g.prize.y += 10000000000000;
-    });
-
+fn fewest_tokens_far_prizes(games: &[Game]) -> u64 {
     let mut total_tokens = 0;
-    for game in data {
+    for game in games {
         let (ax, ay, bx, by, px, py) = (
             game.button_a.x as i64,
             game.button_a.y as i64,
             game.button_b.x as i64,
             game.button_b.y as i64,
-            game.prize.x as i64,
-            game.prize.y as i64,
+            (game.prize.x + 10000000000000) as i64,
+            (game.prize.y + 10000000000000) as i64,
         );
         let num = bx * ax * py - bx * ay * px;
         let div = by * ax - ay * bx;
@@ -114,7 +103,95 @@ pub fn part_two(input: &str) -> Option<String> {
         }
     }
 
-    Some(total_tokens.to_string())
+    total_tokens
+}
+
+/// Solves the two linear equations of each game with Cramer's rule instead of trying all presses.
+fn fewest_tokens_solved(games: &[Game]) -> Option<u64> {
+    let mut total_tokens = 0;
+    for game in games {
+        let (ax, ay, bx, by, px, py) = (
+            game.button_a.x as i64,
+            game.button_a.y as i64,
+            game.button_b.x as i64,
+            game.button_b.y as i64,
+            game.prize.x as i64,
+            game.prize.y as i64,
+        );
+        let presses_b = |button_a: i64| {
+            let button_b = (px - button_a * ax) / bx;
+            ((0..=100).contains(&button_b)
+                && button_a * ax + button_b * bx == px
+                && button_a * ay + button_b * by == py)
+                .then_some(button_b)
+        };
+
+        let det = ax * by - ay * bx;
+        let tokens = if det == 0 {
+            // the buttons move in the same direction, there can be multiple ways to reach the prize.
+            (0..=100)
+                .filter_map(|button_a| presses_b(button_a).map(|button_b| button_a * 3 + button_b))
+                .min()
+        } else {
+            let button_a = (px * by - py * bx) / det;
+            (0..=100)
+                .contains(&button_a)
+                .then(|| presses_b(button_a))
+                .flatten()
+                .map(|button_b| button_a * 3 + button_b)
+        };
+
+        total_tokens += tokens.unwrap_or_default() as u64;
+    }
+
+    Some(total_tokens)
+}
+
+/// Games with small buttons, some of which move in the same direction.
+/// Most prizes can be reached with at most 100 presses per button.
+fn generate_games(rng: &mut Rng) -> String {
+    let games: Vec<String> = (0..rng.range(1..=5))
+        .map(|_| {
+            let (ax, ay) = (rng.range(1..=20), rng.range(1..=20));
+            let (bx, by) = if rng.chance(0.3) {
+                let factor = rng.range(1..=4);
+                (ax * factor, ay * factor)
+            } else {
+                (rng.range(1..=20), rng.range(1..=20))
+            };
+            let (px, py) = if rng.chance(0.8) {
+                let (a, b) = (rng.range(0..=100), rng.range(0..=100));
+                (a * ax + b * bx, a * ay + b * by)
+            } else {
+                (rng.range(0..=2000), rng.range(0..=2000))
+            };
+
+            format!("Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}")
+        })
+        .collect();
+
+    games.join("\n\n") + "\n"
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<Game>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        parse_all(input, separated_list1(tag("\n\n"), parse_part_one))
+    }
+
+    fn part_one(games: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(fewest_tokens(games))
+    }
+
+    fn part_two(games: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(fewest_tokens_far_prizes(games))
+    }
 }
 
 #[cfg(test)]
@@ -123,13 +200,22 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("480".to_string()));
+        let input = Day13::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day13::part_one(&input.unwrap());
+        assert_eq!(result, Some(480));
+    }
+
+    #[test]
+    fn test_part_one_solved() {
+        let input = Day13::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = fewest_tokens_solved(&input.unwrap());
+        assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("875318608908".to_string()));
+        let input = Day13::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day13::part_two(&input.unwrap());
+        assert_eq!(result, Some(875318608908));
     }
 }
@@ -1,75 +1,60 @@
 use core::str;
+use std::cmp::Ordering;
 
-use advent_of_code::{Matrix, Point};
+use advent_of_code::template::{parse_lines, Params, ParseError, Solution};
+use advent_of_code::{Addressing, Matrix, Point};
 use nom::sequence::preceded;
 use nom::{
     bytes::complete::tag,
-    character::complete::{i32, line_ending, space1},
-    multi::separated_list1,
+    character::complete::{i32, space1},
     sequence::separated_pair,
 };
 
-advent_of_code::solution!(14);
-
-const MAX_X: u32 = 101;
-const MAX_Y: u32 = 103;
-const MID_X: u32 = MAX_X / 2;
-const MID_Y: u32 = MAX_Y / 2;
-
-pub fn part_one(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let result = separated_list1(
-        new_line,
-        separated_pair(
-            preceded(tag("p="), separated_pair(i32, tag(","), i32)),
-            space1,
-            preceded(tag("v="), separated_pair(i32, tag(","), i32)),
-        ),
-    )(input);
-    let data = result.expect("Correct input format").1;
+advent_of_code::solution!(14, Day14);
+
+/// The position and velocity of a robot.
+type Robot = ((i32, i32), (i32, i32));
+
+/// The robots and the size of the area they move in.
+pub struct Bathroom {
+    robots: Vec<Robot>,
+    width: i32,
+    height: i32,
+}
+
+fn safety_factor(bathroom: &Bathroom) -> u64 {
+    let (width, height) = (bathroom.width, bathroom.height);
+    let (mid_x, mid_y) = (width / 2, height / 2);
 
     let (mut a, mut b, mut c, mut d) = (0, 0, 0, 0);
 
-    for ((px, py), (vx, vy)) in data {
-        let new_px = (px + vx * 100).rem_euclid(MAX_X as i32) as u32;
-        let new_py = (py + vy * 100).rem_euclid(MAX_Y as i32) as u32;
+    for ((px, py), (vx, vy)) in &bathroom.robots {
+        let new_px = (px + vx * 100).rem_euclid(width);
+        let new_py = (py + vy * 100).rem_euclid(height);
 
-        match (new_px, new_py) {
-            (MID_X..=MID_X, _) => {}
-            (_, MID_Y..=MID_Y) => {}
-            (0..MID_X, 0..MID_Y) => {
+        match (new_px.cmp(&mid_x), new_py.cmp(&mid_y)) {
+            (Ordering::Equal, _) | (_, Ordering::Equal) => {}
+            (Ordering::Less, Ordering::Less) => {
                 a += 1;
             }
-            (0..MID_X, MID_Y..MAX_Y) => {
+            (Ordering::Less, Ordering::Greater) => {
                 b += 1;
             }
-            (MID_X..MAX_X, 0..MID_Y) => {
+            (Ordering::Greater, Ordering::Less) => {
                 c += 1;
             }
-            (MID_X..MAX_X, MID_Y..MAX_Y) => {
+            (Ordering::Greater, Ordering::Greater) => {
                 d += 1;
             }
-            _ => {
-                unreachable!()
-            }
         }
     }
 
-    Some((a * b * c * d).to_string())
+    a * b * c * d
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let result = separated_list1(
-        new_line,
-        separated_pair(
-            preceded(tag("p="), separated_pair(i32, tag(","), i32)),
-            space1,
-            preceded(tag("v="), separated_pair(i32, tag(","), i32)),
-        ),
-    )(input);
-    let parsed_data = result.expect("Correct input format").1;
-    let data: Vec<(Point, Point)> = parsed_data
+fn find_christmas_tree(bathroom: &Bathroom) -> Option<isize> {
+    let data: Vec<(Point, Point)> = bathroom
+        .robots
         .iter()
         .map(|((px, py), (vx, vy))| {
             (
@@ -79,22 +64,18 @@ pub fn part_two(input: &str) -> Option<String> {
         })
         .collect();
 
-    for sec in 0..=101 * 103 {
-        let mut matrix = Matrix::from(
-            MAX_X as usize,
-            MAX_Y as usize,
-            [b'.'; MAX_X as usize * MAX_Y as usize].to_vec(),
-        );
-        for (p, v) in &data {
-            let new_px = (p.0 + v.0 * sec).rem_euclid(MAX_X as isize);
-            let new_py = (p.1 + v.1 * sec).rem_euclid(MAX_Y as isize);
+    let (width, height) = (bathroom.width as usize, bathroom.height as usize);
 
-            matrix.update(&Point(new_px, new_py), b'#');
+    for sec in 0..=(width * height) as isize {
+        let mut matrix = Matrix::from(width, height, vec![b'.'; width * height])
+            .with_addressing(Addressing::Torus);
+        for (p, v) in &data {
+            matrix.update(&(*p + *v * sec), b'#');
         }
 
         if let Ok(s) = str::from_utf8(matrix.data.as_slice()) {
             if s.contains("###########") {
-                return Some(sec.to_string());
+                return Some(sec);
             }
         }
     }
@@ -102,19 +83,66 @@ pub fn part_two(input: &str) -> Option<String> {
     None
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Bathroom;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Parsed, Self::Error> {
+        let robots = parse_lines(
+            input,
+            separated_pair(
+                preceded(tag("p="), separated_pair(i32, tag(","), i32)),
+                space1,
+                preceded(tag("v="), separated_pair(i32, tag(","), i32)),
+            ),
+        )?;
+
+        Ok(Bathroom {
+            robots,
+            width: params.get_or("width", 101),
+            height: params.get_or("height", 103),
+        })
+    }
+
+    fn part_one(bathroom: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(safety_factor(bathroom))
+    }
+
+    fn part_two(bathroom: &Self::Parsed) -> Option<Self::Answer2> {
+        find_christmas_tree(bathroom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::examples::{read_example, read_example_params};
+
+    fn read_input(file: &str) -> Bathroom {
+        Day14::parse_with(
+            &read_example(DAY.into(), file),
+            &read_example_params(DAY.into(), file),
+        )
+        .unwrap()
+    }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("21".to_string()));
+        let result = Day14::part_one(&read_input("14.txt"));
+        assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
-        assert_eq!(result, Some("6446".to_string()));
+        let result = Day14::part_two(&read_input("14-2.txt"));
+        assert_eq!(result, Some(5));
     }
 }
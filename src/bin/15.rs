@@ -1,18 +1,15 @@
 use core::str;
 
+use advent_of_code::template::{ParseError, Solution};
 use advent_of_code::{Direction, Matrix, Point, DOWN, LEFT, RIGHT, UP};
 
-advent_of_code::solution!(15);
-
-pub fn part_one(input: &str) -> Option<String> {
-    let (part_a, part_b) = input
-        .trim()
-        .split_once("\n\n")
-        .expect("Correct input format");
-    let nrows = part_a.as_bytes().iter().filter(|&&c| c == b'\n').count() + 1;
-    let ncols = part_a.find('\n').unwrap();
-    let part_a = &part_a.replace('\n', "");
-    let mut matrix = Matrix::from(nrows, ncols, part_a.as_bytes().to_vec());
+advent_of_code::solution!(15, Day15);
+
+/// The map of the warehouse and the moves of the robot.
+type Warehouse = (Matrix<u8>, String);
+
+fn move_boxes((map, part_b): &Warehouse) -> u64 {
+    let mut matrix = map.clone();
     // println!("{}", matrix);
 
     let part_b = &part_b.replace('\n', "");
@@ -69,7 +66,7 @@ pub fn part_one(input: &str) -> Option<String> {
         }
     }
 
-    Some(acc.to_string())
+    acc
 }
 
 fn can_move(direction: Direction, matrix: &mut Matrix<u8>, guard_pos: Point) -> bool {
@@ -161,20 +158,17 @@ fn move_part(direction: Direction, matrix: &mut Matrix<u8>, pos: Point) -> Point
     new_pos
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (part_a, part_b) = input
-        .trim()
-        .split_once("\n\n")
-        .expect("Correct input format");
-    let nrows = part_a.as_bytes().iter().filter(|&&c| c == b'\n').count() + 1;
-    let ncols = part_a.find('\n').unwrap();
-    let part_a = &part_a
-        .replace('\n', "")
-        .replace('#', "##")
-        .replace('O', "[]")
-        .replace('.', "..")
-        .replace('@', "@.");
-    let mut matrix = Matrix::from(nrows, ncols * 2, part_a.as_bytes().to_vec());
+fn move_wide_boxes((map, part_b): &Warehouse) -> u64 {
+    let wide = map
+        .data
+        .iter()
+        .flat_map(|chr| match chr {
+            b'O' => *b"[]",
+            b'@' => *b"@.",
+            chr => [*chr, *chr],
+        })
+        .collect();
+    let mut matrix = Matrix::from(map.rows as usize, map.cols as usize * 2, wide);
     // println!("{}", matrix);
 
     let part_b = &part_b.replace('\n', "");
@@ -210,7 +204,38 @@ pub fn part_two(input: &str) -> Option<String> {
         }
     }
 
-    Some(acc.to_string())
+    acc
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Warehouse;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        let input = input.trim();
+        input
+            .split_once("\n\n")
+            .ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    &input[input.len()..],
+                    "an empty line between the map and the moves",
+                )
+            })
+            .and_then(|(map, moves)| Ok((Matrix::parse(map)?, moves.to_string())))
+    }
+
+    fn part_one(warehouse: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(move_boxes(warehouse))
+    }
+
+    fn part_two(warehouse: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(move_wide_boxes(warehouse))
+    }
 }
 
 #[cfg(test)]
@@ -219,13 +244,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("10092".to_string()));
+        let input = Day15::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day15::part_one(&input.unwrap());
+        assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("9021".to_string()));
+        let input = Day15::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day15::part_two(&input.unwrap());
+        assert_eq!(result, Some(9021));
     }
 }
@@ -1,227 +1,65 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
+use advent_of_code::search::{dijkstra, Paths};
+use advent_of_code::template::{ParseError, Solution};
 use advent_of_code::{Direction, Matrix, Point, ALL_4_DIRECTIONS, RIGHT};
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, Day16);
 
-fn dfs_part_one(matrix: &mut Matrix<Cell>, start: Point) -> Option<String> {
-    let mut queue: Vec<(Direction, Point, u64)> = Vec::new();
-    queue.push((RIGHT, start, 0));
+/// A reindeer on the maze: its position and the direction it faces.
+type Reindeer = (Point, Direction);
 
-    while let Some((direction, p, current_points)) = queue.pop() {
-        for dir in ALL_4_DIRECTIONS {
-            let next_point = p + dir;
-            let next_cell = matrix.get_mut(&next_point).unwrap();
+/// The cheapest paths through the maze. Moving forward costs 1 point, turning costs 1000 points per quarter turn.
+fn best_paths(matrix: &Matrix<u8>) -> Option<Paths<Reindeer>> {
+    let start = matrix.find(&b'S')?;
+    let end = matrix.find(&b'E')?;
 
-            if next_cell.chr == b'#' {
-                continue;
-            }
-
-            let new_points = current_points
-                + if dir == direction {
+    let successors = |&(position, facing): &Reindeer| {
+        ALL_4_DIRECTIONS
+            .iter()
+            .map(move |&direction| (position + direction, direction))
+            .filter(|(next, _)| matrix.get(next).is_some_and(|chr| *chr != b'#'))
+            .map(move |(next, direction)| {
+                let points = if direction == facing {
                     1
-                } else if dir == direction.opposite_direction() {
+                } else if direction == facing.opposite_direction() {
                     2001
                 } else {
                     1001
                 };
+                ((next, direction), points)
+            })
+    };
 
-            if let Some(old_points) = next_cell.next.get(&dir) {
-                if new_points < *old_points {
-                    next_cell.next.insert(dir, new_points);
-                    queue.push((dir, next_point, new_points));
-                }
-            } else {
-                next_cell.next.insert(dir, new_points);
-                queue.push((dir, next_point, new_points));
-            }
-        }
-    }
-
-    // print_matrix(&matrix);
-
-    let end = matrix.find(&Cell::new(b'E')).unwrap();
-
-    let end_cell = matrix.get(&end).unwrap();
-
-    get_optimal(end_cell).map(|x| x.to_string())
+    dijkstra((start, RIGHT), successors, |(position, _)| *position == end)
 }
 
-#[derive(Clone)]
-struct Cell {
-    chr: u8,
-    next: HashMap<Direction, u64>,
-}
-impl Cell {
-    fn new(chr: u8) -> Self {
-        Self {
-            chr,
-            next: HashMap::new(),
-        }
-    }
-}
-impl PartialEq for Cell {
-    fn eq(&self, other: &Self) -> bool {
-        self.chr == other.chr
-    }
-}
+pub struct Day16;
 
-// fn print_matrix(matrix: &Matrix<Cell>) {
-//     for x in 0..matrix.rows {
-//         if x > 0 {
-//             println!("");
-//         }
-//         for y in 0..matrix.cols {
-//             let value = matrix.get(&Point(x, y)).expect("Checked");
-//             print!("{}", char::from_u32(value.chr as u32).unwrap());
-//             // for n in &value.next {
-//             //     print!("{}{}/", n.0, n.1);
-//             // }
-//             print!("");
-//         }
-//     }
-// }
+impl Solution for Day16 {
+    type Parsed = Matrix<u8>;
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = usize;
 
-pub fn part_one(input: &str) -> Option<String> {
-    let nrows = input
-        .trim()
-        .as_bytes()
-        .iter()
-        .filter(|&&c| c == b'\n')
-        .count()
-        + 1;
-    let ncols = input.trim().find('\n').unwrap();
-    let matrix = Matrix::from(
-        nrows,
-        ncols,
-        input
-            .trim()
-            .replace('\n', "")
-            .as_bytes()
-            .iter()
-            .map(|chr| Cell::new(*chr))
-            .collect(),
-    );
-
-    let start = matrix.find(&Cell::new(b'S')).unwrap();
-
-    // println!("{matrix}");
-    // println!("{start} {end}");
-
-    dfs_part_one(&mut matrix.clone(), start)
-}
-
-fn get_optimal(cell: &Cell) -> Option<u64> {
-    cell.next.values().min().copied()
-}
-
-fn dfs_part_two(matrix: &mut Matrix<Cell>, start: Point) -> Option<String> {
-    let mut queue: Vec<(Direction, Point, u64)> = Vec::new();
-    queue.push((RIGHT, start, 0));
-
-    while let Some((direction, p, current_points)) = queue.pop() {
-        for dir in ALL_4_DIRECTIONS {
-            let next_point = p + dir;
-            let next_cell = matrix.get_mut(&next_point).unwrap();
-
-            if next_cell.chr == b'#' {
-                continue;
-            }
-
-            let new_points = current_points
-                + if dir == direction {
-                    1
-                } else if dir == direction.opposite_direction() {
-                    2001
-                } else {
-                    1001
-                };
-
-            if let Some(old_points) = next_cell.next.get(&dir) {
-                if new_points < *old_points {
-                    next_cell.next.insert(dir, new_points);
-                    queue.push((dir, next_point, new_points));
-                }
-            } else {
-                next_cell.next.insert(dir, new_points);
-                queue.push((dir, next_point, new_points));
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Matrix::parse(input)
     }
 
-    // print_matrix(&matrix);
-
-    let end = matrix.find(&Cell::new(b'E')).unwrap();
-
-    let end_cell = matrix.get(&end).unwrap();
-    let optimal_value = get_optimal(end_cell).unwrap();
-
-    for n in &end_cell.next {
-        if *n.1 == optimal_value {
-            queue.push((*n.0, end, *n.1));
-        }
+    fn part_one(matrix: &Self::Parsed) -> Option<Self::Answer1> {
+        best_paths(matrix).map(|paths| paths.cost())
     }
 
-    let mut paths = HashSet::new();
-
-    while let Some((direction, p, current_points)) = queue.pop() {
-        // for dir in ALL_4_DIRECTIONS {
-        let old_point = p + direction.opposite_direction();
-        let old_cell = matrix.get(&old_point).unwrap();
-
-        for (dir, points) in &old_cell.next {
-            let expected_points = current_points
-                - if *dir == direction {
-                    1
-                } else if *dir == direction.opposite_direction() {
-                    2001
-                } else {
-                    1001
-                };
-
-            if expected_points == *points {
-                paths.insert(old_point);
-                queue.push((*dir, old_point, expected_points));
-            }
-        }
-    }
+    fn part_two(matrix: &Self::Parsed) -> Option<Self::Answer2> {
+        let paths = best_paths(matrix)?;
+        let tiles: HashSet<Point> = paths
+            .nodes_on_paths()
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
 
-    for path in &paths {
-        matrix.get_mut(path).unwrap().chr = b'O';
+        Some(tiles.len())
     }
-
-    // print_matrix(&matrix);
-
-    Some((2 + paths.len() as u64).to_string())
-}
-
-pub fn part_two(input: &str) -> Option<String> {
-    let nrows = input
-        .trim()
-        .as_bytes()
-        .iter()
-        .filter(|&&c| c == b'\n')
-        .count()
-        + 1;
-    let ncols = input.trim().find('\n').unwrap();
-    let matrix = Matrix::from(
-        nrows,
-        ncols,
-        input
-            .trim()
-            .replace('\n', "")
-            .as_bytes()
-            .iter()
-            .map(|chr| Cell::new(*chr))
-            .collect(),
-    );
-
-    let start = matrix.find(&Cell::new(b'S')).unwrap();
-
-    // println!("{matrix}");
-    // println!("{start} {end}");
-
-    dfs_part_two(&mut matrix.clone(), start)
 }
 
 #[cfg(test)]
@@ -230,13 +68,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("11048".to_string()));
+        let input = Day16::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day16::part_one(&input.unwrap());
+        assert_eq!(result, Some(11048));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("64".to_string()));
+        let input = Day16::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day16::part_two(&input.unwrap());
+        assert_eq!(result, Some(64));
     }
 }
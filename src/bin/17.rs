@@ -1,3 +1,4 @@
+use advent_of_code::template::{parse_all, Params, ParseError, Solution};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
@@ -8,10 +9,10 @@ use nom::{
 };
 use num_traits::*;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, Day17);
 
 #[derive(Debug, Clone)]
-struct CpuState {
+pub struct CpuState {
     regs: [u64; 3],
 }
 
@@ -49,6 +50,13 @@ impl num_traits::FromPrimitive for Operand {
 
 type Commands = Vec<(u8, u8)>;
 
+/// The initial state and program of the computer.
+pub struct Computer {
+    state: CpuState,
+    cmds: Commands,
+    max_prefix: usize,
+}
+
 fn parse_input(input: &str) -> IResult<&str, (CpuState, Commands)> {
     let (input, _) = tag("Register A: ")(input)?;
     let (input, a) = u64(input)?;
@@ -123,16 +131,8 @@ fn execute_machine(mut state: CpuState, cmds: &[(u8, u8)]) -> Vec<u8> {
     output
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let (state, cmds) = parse_input(input.trim()).expect("Correct input format").1;
-
-    let output = execute_machine(state, &cmds);
-
-    Some(output.iter().join(",").to_string())
-}
-
-pub fn part_two(input: &str) -> Option<String> {
-    let (mut state, cmds) = parse_input(input.trim()).expect("Correct input format").1;
+fn find_quine(computer: &Computer) -> u64 {
+    let (mut state, cmds) = (computer.state.clone(), &computer.cmds);
 
     let flat_cmd: Vec<u8> = cmds
         .iter()
@@ -140,33 +140,24 @@ pub fn part_two(input: &str) -> Option<String> {
         .flatten()
         .collect();
     let mut solution = 8u64.pow(flat_cmd.len() as u32 - 1);
+    // prefixes are compared in steps of two instructions, an odd maximum would skip every even length.
+    let max_prefix = computer.max_prefix & !1;
     let mut new_inc = 1;
     let mut inc = 1;
     loop {
         // println!("SOLUTION: {solution}");
         state.regs[0] = solution;
 
-        let output = execute_machine(state.clone(), &cmds);
+        let output = execute_machine(state.clone(), cmds);
 
         if output.len() == flat_cmd.len() {
-            if cfg!(test) {
-                if output[..6] == flat_cmd[..6] {
-                    new_inc = 8u64.pow(4);
-                } else if output[..4] == flat_cmd[..4] {
-                    new_inc = 8u64.pow(2);
-                }
-            } else if output[..14] == flat_cmd[..14] {
-                new_inc = 8u64.pow(12);
-            } else if output[..12] == flat_cmd[..12] {
-                new_inc = 8u64.pow(10);
-            } else if output[..10] == flat_cmd[..10] {
-                new_inc = 8u64.pow(8);
-            } else if output[..8] == flat_cmd[..8] {
-                new_inc = 8u64.pow(6);
-            } else if output[..6] == flat_cmd[..6] {
-                new_inc = 8u64.pow(4);
-            } else if output[..4] == flat_cmd[..4] {
-                new_inc = 8u64.pow(2);
+            // the longer the matching prefix, the larger the steps.
+            if let Some(prefix) = (4..=max_prefix)
+                .rev()
+                .step_by(2)
+                .find(|&prefix| output[..prefix] == flat_cmd[..prefix])
+            {
+                new_inc = 8u64.pow(prefix as u32 - 2);
             }
 
             if new_inc > inc {
@@ -181,23 +172,74 @@ pub fn part_two(input: &str) -> Option<String> {
         solution += inc;
     }
 
-    Some(solution.to_string())
+    solution
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = Computer;
+    type Error = ParseError;
+    type Answer1 = String;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Parsed, Self::Error> {
+        let (state, cmds) = parse_all(input, parse_input)?;
+
+        Ok(Computer {
+            state,
+            cmds,
+            max_prefix: params.get_or("max_prefix", 14),
+        })
+    }
+
+    fn part_one(computer: &Self::Parsed) -> Option<Self::Answer1> {
+        let output = execute_machine(computer.state.clone(), &computer.cmds);
+
+        Some(output.iter().join(","))
+    }
+
+    fn part_two(computer: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(find_quine(computer))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn read_example() -> Computer {
+        Day17::parse_with(
+            &advent_of_code::template::read_file("examples", DAY),
+            &advent_of_code::template::read_params("examples", DAY),
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day17::part_one(&read_example());
         assert_eq!(result, Some("5,7,3,0".to_string()));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("117440".to_string()));
+        let result = Day17::part_two(&read_example());
+        assert_eq!(result, Some(117440));
+    }
+
+    #[test]
+    fn test_part_two_odd_max_prefix() {
+        let computer = Day17::parse_with(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::default().with("max_prefix", 7),
+        )
+        .unwrap();
+        assert_eq!(Day17::part_two(&computer), Some(117440));
     }
 }
 
@@ -1,113 +1,110 @@
-use advent_of_code::{Matrix, Point, ALL_4_DIRECTIONS};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{line_ending, u32},
-    multi::separated_list1,
-    sequence::separated_pair,
-};
-use pathfinding::prelude::astar;
-
-advent_of_code::solution!(18);
-
-fn find_successors(matrix: &Matrix<u8>, point: &Point) -> Vec<(Point, usize)> {
-    let mut next = Vec::new();
-    for direction in ALL_4_DIRECTIONS {
-        let new_point = *point + direction;
-        if let Some(chr) = matrix.get(&new_point) {
-            if *chr == b'.' {
-                next.push((new_point, 1));
-            }
+use advent_of_code::template::{parse_lines, Params, ParseError, Solution};
+use advent_of_code::{Matrix, Point};
+use nom::{bytes::complete::tag, character::complete::u32, sequence::separated_pair};
+
+advent_of_code::solution!(18, Day18);
+
+/// The falling bytes and the size of the memory space.
+pub struct Memory {
+    bytes: Vec<(u32, u32)>,
+    size: usize,
+    num_rocks: usize,
+}
+
+impl Memory {
+    /// The memory space after the first `num_rocks` bytes have fallen.
+    fn corrupted(&self) -> Matrix<u8> {
+        let mut matrix = Matrix::empty(self.size, self.size, b'.');
+
+        for (y, x) in &self.bytes[..self.num_rocks] {
+            matrix.update(&Point(*x as isize, *y as isize), b'#');
         }
-    }
 
-    next
+        matrix
+    }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let data = separated_list1(new_line, separated_pair(u32, tag(","), u32))(input)
-        .expect("Correct input format")
-        .1;
+pub struct Day18;
 
-    let size = if cfg!(test) { 7 } else { 71 };
-    let num_rocks = if cfg!(test) { 12 } else { 1024 };
+impl Solution for Day18 {
+    type Parsed = Memory;
+    type Error = ParseError;
+    type Answer1 = usize;
+    type Answer2 = String;
 
-    let mut matrix = Matrix::empty(size, size, b'.');
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Self::parse_with(input, &Params::default())
+    }
 
-    let start = Point(0, 0);
-    let end = Point(size as isize - 1, size as isize - 1);
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Parsed, Self::Error> {
+        let bytes = parse_lines(input, separated_pair(u32, tag(","), u32))?;
 
-    for (y, x) in &data[..num_rocks] {
-        matrix.update(&Point(*x as isize, *y as isize), b'#');
+        Ok(Memory {
+            bytes,
+            size: params.get_or("size", 71),
+            num_rocks: params.get_or("num_rocks", 1024),
+        })
     }
 
-    let result = astar(
-        &start,
-        |p| find_successors(&matrix, p),
-        |p| ((p.0 - end.0).abs() + (p.1 - end.1).abs()) as usize,
-        |p| *p == end,
-    )
-    .unwrap()
-    .1;
+    fn part_one(memory: &Self::Parsed) -> Option<Self::Answer1> {
+        let matrix = memory.corrupted();
 
-    Some(result.to_string())
-}
+        let start = Point(0, 0);
+        let end = Point(memory.size as isize - 1, memory.size as isize - 1);
 
-pub fn part_two(input: &str) -> Option<String> {
-    let new_line = line_ending::<&str, ()>;
-    let data = separated_list1(new_line, separated_pair(u32, tag(","), u32))(input)
-        .expect("Correct input format")
-        .1;
+        let paths = matrix.shortest_paths(&start, &end, |chr| *chr == b'.')?;
 
-    let size = if cfg!(test) { 7 } else { 71 };
-    let num_rocks = if cfg!(test) { 12 } else { 1024 };
+        Some(paths.cost() as usize)
+    }
 
-    let mut matrix = Matrix::empty(size, size, b'.');
+    fn part_two(memory: &Self::Parsed) -> Option<Self::Answer2> {
+        let mut matrix = memory.corrupted();
+        let data = &memory.bytes;
 
-    let start = Point(0, 0);
-    let end = Point(size as isize - 1, size as isize - 1);
+        let start = Point(0, 0);
+        let end = Point(memory.size as isize - 1, memory.size as isize - 1);
 
-    for (y, x) in &data[..num_rocks] {
-        matrix.update(&Point(*x as isize, *y as isize), b'#');
-    }
+        let mut rock = 0;
+        loop {
+            let (y, x) = data[rock];
+            matrix.update(&Point(x as isize, y as isize), b'#');
 
-    let mut rock = 0;
-    loop {
-        let (y, x) = data[rock];
-        matrix.update(&Point(x as isize, y as isize), b'#');
+            let paths = matrix.shortest_paths(&start, &end, |chr| *chr == b'.');
 
-        let result = astar(
-            &start,
-            |p| find_successors(&matrix, p),
-            |p| ((p.0 - end.0).abs() + (p.1 - end.1).abs()) as usize,
-            |p| *p == end,
-        );
+            if paths.is_none() {
+                break;
+            }
 
-        if result.is_none() {
-            break;
+            rock += 1;
         }
 
-        rock += 1;
-    }
-
-    let (y, x) = data[rock];
+        let (y, x) = data[rock];
 
-    Some(format!("{y},{x}"))
+        Some(format!("{y},{x}"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn read_example() -> Memory {
+        Day18::parse_with(
+            &advent_of_code::template::read_file("examples", DAY),
+            &advent_of_code::template::read_params("examples", DAY),
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("22".to_string()));
+        let result = Day18::part_one(&read_example());
+        assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day18::part_two(&read_example());
         assert_eq!(result, Some("6,1".to_string()));
     }
 }
@@ -1,8 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::template::{parse_all, ParseError, Solution};
 use nom::{bytes::complete::tag, character::complete::alpha1, multi::separated_list1, IResult};
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(19, Day19);
 
 fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     let (input, patterns) = separated_list1(tag(", "), alpha1)(input)?;
@@ -12,7 +13,7 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     Ok((input, (patterns, designs)))
 }
 
-fn check(patterns: &Vec<&str>, design: &str) -> bool {
+fn check(patterns: &[String], design: &str) -> bool {
     let mut queue = HashSet::new();
     queue.insert(design);
 
@@ -24,7 +25,7 @@ fn check(patterns: &Vec<&str>, design: &str) -> bool {
         }
         // println!("Trying: {d}");
         for pattern in patterns {
-            if let Some(substring) = d.strip_prefix(pattern) {
+            if let Some(substring) = d.strip_prefix(pattern.as_str()) {
                 queue.insert(substring);
             }
         }
@@ -33,20 +34,7 @@ fn check(patterns: &Vec<&str>, design: &str) -> bool {
     false
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let (patterns, designs) = parse_input(input).expect("Correct input format").1;
-
-    let mut acc = 0;
-    for design in designs {
-        if check(&patterns, design) {
-            acc += 1;
-        }
-    }
-
-    Some(acc.to_string())
-}
-
-fn count(patterns: &Vec<&str>, design: &str) -> u64 {
+fn count(patterns: &[String], design: &str) -> u64 {
     let mut queue = HashMap::new();
     queue.insert(design, 1);
     let mut acc = 0;
@@ -64,7 +52,7 @@ fn count(patterns: &Vec<&str>, design: &str) -> u64 {
         }
 
         for pattern in patterns {
-            if let Some(substring) = old_key.strip_prefix(pattern) {
+            if let Some(substring) = old_key.strip_prefix(pattern.as_str()) {
                 let mut val = old_val;
                 if queue.contains_key(substring) {
                     val += *queue.get(substring).unwrap();
@@ -77,15 +65,41 @@ fn count(patterns: &Vec<&str>, design: &str) -> u64 {
     acc
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (patterns, designs) = parse_input(input).expect("Correct input format").1;
+pub struct Day19;
 
-    let mut acc = 0;
-    for design in designs {
-        acc += count(&patterns, design);
+impl Solution for Day19 {
+    /// The available towel patterns and the desired designs.
+    type Parsed = (Vec<String>, Vec<String>);
+    type Error = ParseError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        let (patterns, designs) = parse_all(input, parse_input)?;
+        let owned = |x: Vec<&str>| x.into_iter().map(String::from).collect();
+
+        Ok((owned(patterns), owned(designs)))
     }
 
-    Some(acc.to_string())
+    fn part_one((patterns, designs): &Self::Parsed) -> Option<Self::Answer1> {
+        let mut acc = 0;
+        for design in designs {
+            if check(patterns, design) {
+                acc += 1;
+            }
+        }
+
+        Some(acc)
+    }
+
+    fn part_two((patterns, designs): &Self::Parsed) -> Option<Self::Answer2> {
+        let mut acc = 0;
+        for design in designs {
+            acc += count(patterns, design);
+        }
+
+        Some(acc)
+    }
 }
 
 #[cfg(test)]
@@ -94,13 +108,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6".to_string()));
+        let input = Day19::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day19::part_one(&input.unwrap());
+        assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("16".to_string()));
+        let input = Day19::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day19::part_two(&input.unwrap());
+        assert_eq!(result, Some(16));
     }
 }
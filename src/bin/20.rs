@@ -1,73 +1,62 @@
-use advent_of_code::{Matrix, Point, ALL_4_DIRECTIONS, LEFT};
+use advent_of_code::template::{Params, ParseError, Solution};
+use advent_of_code::{Matrix, ALL_4_DIRECTIONS, LEFT};
 use itertools::Itertools;
-use pathfinding::prelude::astar;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, Day20, variants { 1 => part_one_distances });
 
-fn find_successors(matrix: &Matrix<u8>, point: &Point) -> Vec<(Point, usize)> {
-    let mut next = Vec::new();
-    for direction in ALL_4_DIRECTIONS {
-        let new_point = *point + direction;
-        if let Some(chr) = matrix.get(&new_point) {
-            if *chr != b'#' {
-                next.push((new_point, 1));
-            }
-        }
-    }
-
-    next
+/// The racetrack and the picoseconds a cheat has to save to be counted.
+pub struct Racetrack {
+    matrix: Matrix<u8>,
+    part_one_threshold: isize,
+    part_two_threshold: isize,
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
+fn count_wall_cheats(racetrack: &Racetrack) -> usize {
+    let matrix = &racetrack.matrix;
 
     let start = matrix.find(&b'S').unwrap();
     let end = matrix.find(&b'E').unwrap();
 
     // println!("{matrix}\n{start}\n{end}");
 
-    let perfect = astar(
-        &start,
-        |p| find_successors(&matrix, p),
-        |p| p.distance(end) as usize,
-        |p| *p == end,
-    )
-    .expect("ERROR: At least one solution to the maze!")
-    .1;
+    let perfect = matrix
+        .shortest_paths(&start, &end, |chr| *chr != b'#')
+        .expect("ERROR: At least one solution to the maze!")
+        .cost();
 
+    let threshold = racetrack.part_one_threshold as u64;
     let mut acc = 0;
 
     for p in matrix.as_points() {
         if *matrix.get(&p).unwrap() == b'#' {
             let mut new_matrix = matrix.clone();
             new_matrix.update(&p, b'.');
-            let result = astar(
-                &start,
-                |p| find_successors(&new_matrix, p),
-                |p| p.distance(end) as usize,
-                |p| *p == end,
-            )
-            .expect("ERROR: At least one solution to the maze!")
-            .1;
+            let result = new_matrix
+                .shortest_paths(&start, &end, |chr| *chr != b'#')
+                .expect("ERROR: At least one solution to the maze!")
+                .cost();
 
             let saved = perfect - result;
-            let threshold = if cfg!(test) { 1 } else { 100 };
             if saved >= threshold {
                 acc += 1;
             }
         }
     }
 
-    Some(acc.to_string())
+    acc
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let rows = input.trim().split('\n').collect::<Vec<&str>>();
-
-    let matrix = Matrix::from(rows.len(), rows.len(), rows.join("").into());
+/// Compares the distances along the track instead of searching the maze again for every wall.
+pub fn part_one_distances(racetrack: &Racetrack) -> Option<usize> {
+    Some(count_cheats(
+        &racetrack.matrix,
+        2,
+        racetrack.part_one_threshold,
+    ))
+}
 
+/// Count the cheats of at most `max_cheat` picoseconds that save at least `threshold` picoseconds.
+fn count_cheats(matrix: &Matrix<u8>, max_cheat: isize, threshold: isize) -> usize {
     let start = matrix.find(&b'S').unwrap();
     let end = matrix.find(&b'E').unwrap();
     let mut current_direction = LEFT;
@@ -103,28 +92,74 @@ pub fn part_two(input: &str) -> Option<String> {
     for pair in all_points.into_iter().permutations(2) {
         let (a, b) = (pair.first().unwrap(), pair.last().unwrap());
 
-        let threshold = if cfg!(test) { 50 } else { 100 };
-        if a.0.distance(b.0) <= 20 && a.1 - b.1 - a.0.distance(b.0) >= threshold {
+        if a.0.distance(b.0) <= max_cheat && a.1 - b.1 - a.0.distance(b.0) >= threshold {
             acc += 1;
         }
     }
 
-    Some(acc.to_string())
+    acc
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Racetrack;
+    type Error = ParseError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Parsed, Self::Error> {
+        Ok(Racetrack {
+            matrix: Matrix::parse(input)?,
+            part_one_threshold: params.get_or("part_one_threshold", 100),
+            part_two_threshold: params.get_or("part_two_threshold", 100),
+        })
+    }
+
+    fn part_one(racetrack: &Self::Parsed) -> Option<Self::Answer1> {
+        Some(count_wall_cheats(racetrack))
+    }
+
+    fn part_two(racetrack: &Self::Parsed) -> Option<Self::Answer2> {
+        Some(count_cheats(
+            &racetrack.matrix,
+            20,
+            racetrack.part_two_threshold,
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn read_example() -> Racetrack {
+        Day20::parse_with(
+            &advent_of_code::template::read_file("examples", DAY),
+            &advent_of_code::template::read_params("examples", DAY),
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("44".to_string()));
+        let result = Day20::part_one(&read_example());
+        assert_eq!(result, Some(44));
+    }
+
+    #[test]
+    fn test_part_one_distances() {
+        let result = part_one_distances(&read_example());
+        assert_eq!(result, Some(44));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("285".to_string()));
+        let result = Day20::part_two(&read_example());
+        assert_eq!(result, Some(285));
     }
 }
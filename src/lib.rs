@@ -1,3 +1,4 @@
+pub mod search;
 pub mod template;
 
 use core::str;
@@ -6,9 +7,11 @@ use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Write;
+use std::iter;
 use std::sync::LazyLock;
 
 use forward_ref::{forward_ref_binop, forward_ref_op_assign};
+use template::ParseError;
 
 // Use this file to add helper functions and additional modules.
 
@@ -17,6 +20,17 @@ pub struct Matrix<T> {
     pub rows: isize,
     pub cols: isize,
     pub data: Vec<T>,
+    pub addressing: Addressing,
+}
+
+/// How a [`Matrix`] treats points outside of the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Addressing {
+    /// Points outside of the grid have no cell.
+    #[default]
+    Bounded,
+    /// The grid wraps around at its edges, e.g. `Point(-1, 0)` is the first cell of the last row.
+    Torus,
 }
 
 impl<T: Clone> Matrix<T> {
@@ -25,25 +39,149 @@ impl<T: Clone> Matrix<T> {
             cols: cols as isize,
             rows: rows as isize,
             data: vec![filler; rows * cols],
+            addressing: Addressing::Bounded,
+        }
+    }
+
+    /// Mirror the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Matrix<T> {
+        self.remap(self.cols, self.rows, |Point(row, col)| Point(col, row))
+    }
+
+    /// Rotate the grid clockwise by a quarter turn.
+    pub fn rotate_cw(&self) -> Matrix<T> {
+        self.remap(self.cols, self.rows, |Point(row, col)| {
+            Point(self.rows - 1 - col, row)
+        })
+    }
+
+    /// Rotate the grid counterclockwise by a quarter turn.
+    pub fn rotate_ccw(&self) -> Matrix<T> {
+        self.remap(self.cols, self.rows, |Point(row, col)| {
+            Point(col, self.cols - 1 - row)
+        })
+    }
+
+    /// Mirror the grid horizontally, reversing every row.
+    pub fn flip_h(&self) -> Matrix<T> {
+        self.remap(self.rows, self.cols, |Point(row, col)| {
+            Point(row, self.cols - 1 - col)
+        })
+    }
+
+    /// Mirror the grid vertically, reversing the order of the rows.
+    pub fn flip_v(&self) -> Matrix<T> {
+        self.remap(self.rows, self.cols, |Point(row, col)| {
+            Point(self.rows - 1 - row, col)
+        })
+    }
+
+    /// Build a grid of `rows` by `cols` cells, taking every cell from the point `source` maps it to.
+    fn remap(&self, rows: isize, cols: isize, source: impl Fn(Point) -> Point) -> Matrix<T> {
+        let data = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| Point(row, col)))
+            .map(|point| self.cell(&source(point)).expect("Checked").clone())
+            .collect();
+
+        Matrix {
+            rows,
+            cols,
+            data,
+            addressing: self.addressing,
         }
     }
 }
 
-impl<T: PartialEq> Matrix<T> {
+impl Matrix<u8> {
+    /// Parse a grid of characters, one row per line. See [`Matrix::parse_with`].
+    pub fn parse(input: &str) -> Result<Matrix<u8>, ParseError> {
+        Matrix::parse_with(input, |chr| chr)
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Parse a grid with one row per line, converting every character into a cell.
+    /// The size is taken from the input, surrounding empty lines are ignored.
+    ///
+    /// Fails if a row is not as wide as the first one, or if the input has Windows line endings (CRLF).
+    pub fn parse_with(input: &str, mut cell: impl FnMut(u8) -> T) -> Result<Matrix<T>, ParseError> {
+        let grid = input.trim_matches('\n');
+        let cols = grid.split('\n').next().map_or(0, str::len);
+
+        if cols == 0 {
+            return Err(ParseError::at(input, grid, "a row of the grid"));
+        }
+
+        let mut data = Vec::with_capacity(grid.len());
+        let mut rows = 0;
+
+        for line in grid.split('\n') {
+            if let Some(pos) = line.find('\r') {
+                return Err(ParseError::at(input, &line[pos..], "the end of the line"));
+            }
+            if line.len() != cols {
+                return Err(ParseError::at(
+                    input,
+                    &line[line.len().min(cols)..],
+                    format!("a row as wide as the first one ({cols} cells)"),
+                ));
+            }
+
+            data.extend(line.bytes().map(&mut cell));
+            rows += 1;
+        }
+
+        Ok(Matrix {
+            rows,
+            cols: cols as isize,
+            data,
+            addressing: Addressing::Bounded,
+        })
+    }
+}
+
+impl<T> Matrix<T> {
     pub fn from(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
         assert_eq!(data.len(), rows * cols);
         Matrix {
             cols: cols as isize,
             rows: rows as isize,
             data,
+            addressing: Addressing::Bounded,
         }
     }
 
-    pub fn get(&self, point: &Point) -> Option<&T> {
-        if point.0 < 0 || point.1 < 0 || point.0 >= self.rows || point.1 >= self.cols {
-            return None;
+    /// Change how points outside of the grid are treated by [`get`](Matrix::get), [`get_mut`](Matrix::get_mut),
+    /// [`update`](Matrix::update) and the neighbour iterators.
+    pub fn with_addressing(mut self, addressing: Addressing) -> Matrix<T> {
+        self.addressing = addressing;
+        self
+    }
+
+    /// The point of the grid a point refers to: the point itself, or the point it wraps around to on a torus.
+    /// Returns [`None`] for points outside of a bounded grid.
+    pub fn resolve(&self, point: &Point) -> Option<Point> {
+        match self.addressing {
+            Addressing::Bounded => {
+                if point.0 < 0 || point.1 < 0 || point.0 >= self.rows || point.1 >= self.cols {
+                    return None;
+                }
+                Some(*point)
+            }
+            Addressing::Torus => {
+                if self.rows == 0 || self.cols == 0 {
+                    return None;
+                }
+                Some(Point(
+                    point.0.rem_euclid(self.rows),
+                    point.1.rem_euclid(self.cols),
+                ))
+            }
         }
+    }
 
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        let point = self.resolve(point)?;
         let pos = point.0 * self.cols + point.1;
 
         let chr = self.data.get(pos as usize).expect("Checked");
@@ -51,10 +189,7 @@ impl<T: PartialEq> Matrix<T> {
     }
 
     pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
-        if point.0 < 0 || point.1 < 0 || point.0 >= self.rows || point.1 >= self.cols {
-            return None;
-        }
-
+        let point = self.resolve(point)?;
         let pos = point.0 * self.cols + point.1;
 
         let chr = self.data.get_mut(pos as usize).expect("Checked");
@@ -62,21 +197,152 @@ impl<T: PartialEq> Matrix<T> {
     }
 
     pub fn update(&mut self, point: &Point, chr: T) -> Option<T> {
-        if point.0 < 0 || point.1 < 0 || point.0 >= self.rows || point.1 >= self.cols {
-            return None;
-        }
-
+        let point = self.resolve(point)?;
         let pos = (point.0 * self.cols + point.1) as usize;
         let old = self.data.splice(pos..=pos, [chr]).last();
         old
     }
 
+    /// The cells above, below, left and right of a point, with the points they are at.
+    /// Neighbours outside of a bounded grid are skipped, on a torus they wrap around. Every neighbour is returned
+    /// once, even where the wrapped neighbours coincide on a torus of less than three rows or columns, and a point is
+    /// never its own neighbour.
+    pub fn neighbors4<'a>(&'a self, point: &Point) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let point = *point;
+        let distinct = self.distinct_offsets(&ALL_4_DIRECTIONS.map(Direction::as_point));
+        ALL_4_DIRECTIONS
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| distinct & (1 << i) != 0)
+            .filter_map(move |(_, direction)| self.neighbor(point + *direction))
+    }
+
+    /// Like [`neighbors4`](Matrix::neighbors4), including the diagonal neighbours.
+    pub fn neighbors8<'a>(&'a self, point: &Point) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let point = *point;
+        let distinct = self.distinct_offsets(&*ALL_8_POINTS);
+        ALL_8_POINTS
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| distinct & (1 << i) != 0)
+            .filter_map(move |(_, offset)| self.neighbor(point + offset))
+    }
+
+    fn neighbor(&self, point: Point) -> Option<(Point, &T)> {
+        let point = self.resolve(&point)?;
+        self.get(&point).map(|value| (point, value))
+    }
+
+    /// The offsets leading to distinct cells other than the point itself, as a bitmask over `offsets`.
+    /// Only on a torus of less than three rows or columns do offsets wrap onto the same cell, which does not depend
+    /// on the point they are added to.
+    fn distinct_offsets(&self, offsets: &[Point]) -> u8 {
+        if self.addressing == Addressing::Bounded
+            || (self.rows >= 3 && self.cols >= 3)
+            || self.rows == 0
+            || self.cols == 0
+        {
+            return u8::MAX;
+        }
+
+        let wrap = |offset: &Point| {
+            Point(
+                offset.0.rem_euclid(self.rows),
+                offset.1.rem_euclid(self.cols),
+            )
+        };
+        let mut distinct = 0;
+        for (i, offset) in offsets.iter().enumerate() {
+            let cell = wrap(offset);
+            if cell != Point(0, 0) && !offsets[..i].iter().any(|other| wrap(other) == cell) {
+                distinct |= 1 << i;
+            }
+        }
+        distinct
+    }
+
     pub fn as_points<'a>(&self) -> Box<dyn Iterator<Item = Point> + 'a> {
         let rows = self.rows;
         let cols = self.cols;
         Box::from((0..rows).flat_map(move |x| (0..cols).map(move |y| Point(x, y))))
     }
 
+    /// The cells of a row, from left to right.
+    ///
+    /// # Panics
+    /// If the row is outside of the grid.
+    pub fn row(&self, row: isize) -> &[T] {
+        assert!(
+            0 <= row && row < self.rows,
+            "row {row} is outside of a grid with {} rows",
+            self.rows
+        );
+        let start = (row * self.cols) as usize;
+        &self.data[start..start + self.cols as usize]
+    }
+
+    /// The cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    /// If the column is outside of the grid.
+    pub fn column(&self, col: isize) -> impl Iterator<Item = &T> {
+        assert!(
+            0 <= col && col < self.cols,
+            "column {col} is outside of a grid with {} columns",
+            self.cols
+        );
+        let step = self.cols.max(1) as usize;
+        self.data.iter().skip(col as usize).step_by(step)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.cols.max(1) as usize)
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// The cells from a point in steps of `step`, starting with the point itself and ending at the edge of the grid.
+    /// Rays do not wrap around on a torus.
+    pub fn ray(&self, start: &Point, step: Point) -> impl Iterator<Item = (Point, &T)> {
+        iter::successors(Some(*start), move |point| Some(*point + step))
+            .map_while(|point| self.cell(&point).map(|value| (point, value)))
+    }
+
+    /// The diagonals going down and to the right, starting with the one from the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.cols)
+            .rev()
+            .map(|col| Point(0, col))
+            .chain((1..self.rows).map(|row| Point(row, 0)));
+        starts.map(|start| self.ray(&start, DOWN + RIGHT).map(|(_, value)| value))
+    }
+
+    /// The diagonals going down and to the left, starting with the one from the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.cols)
+            .map(|col| Point(0, col))
+            .chain((1..self.rows).map(|row| Point(row, self.cols - 1)));
+        starts.map(|start| self.ray(&start, DOWN + LEFT).map(|(_, value)| value))
+    }
+
+    /// Borrow the rectangle of `rows` by `cols` cells with its top left corner at `origin`.
+    /// Returns [`None`] if the rectangle does not fit into the grid.
+    pub fn view(&self, origin: &Point, rows: isize, cols: isize) -> Option<MatrixView<'_, T>> {
+        MatrixView::new(self, *origin, rows, cols)
+    }
+
+    /// The cell at a point of the grid, regardless of the addressing.
+    fn cell(&self, point: &Point) -> Option<&T> {
+        if point.0 < 0 || point.1 < 0 || point.0 >= self.rows || point.1 >= self.cols {
+            return None;
+        }
+        self.data.get((point.0 * self.cols + point.1) as usize)
+    }
+}
+
+impl<T: PartialEq> Matrix<T> {
     pub fn find(&self, needle: &T) -> Option<Point> {
         for p in self.as_points() {
             if let Some(chr) = self.get(&p) {
@@ -120,6 +386,209 @@ impl<T: Display + PartialEq> Debug for Matrix<T> {
     }
 }
 
+/// A rectangular part of a [`Matrix`], borrowed from it. Points are relative to the top left corner of the view.
+pub struct MatrixView<'a, T> {
+    matrix: &'a Matrix<T>,
+    origin: Point,
+    pub rows: isize,
+    pub cols: isize,
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    fn new(matrix: &'a Matrix<T>, origin: Point, rows: isize, cols: isize) -> Option<Self> {
+        let fits = origin.0 >= 0
+            && origin.1 >= 0
+            && rows >= 0
+            && cols >= 0
+            && origin.0 + rows <= matrix.rows
+            && origin.1 + cols <= matrix.cols;
+
+        fits.then_some(MatrixView {
+            matrix,
+            origin,
+            rows,
+            cols,
+        })
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&'a T> {
+        if point.0 < 0 || point.1 < 0 || point.0 >= self.rows || point.1 >= self.cols {
+            return None;
+        }
+        self.matrix.cell(&(self.origin + point))
+    }
+
+    /// The cells of a row of the view, from left to right.
+    ///
+    /// # Panics
+    /// If the row is outside of the view.
+    pub fn row(&self, row: isize) -> &'a [T] {
+        assert!(
+            0 <= row && row < self.rows,
+            "row {row} is outside of a view with {} rows",
+            self.rows
+        );
+        let start = (self.origin.1) as usize;
+        &self.matrix.row(self.origin.0 + row)[start..start + self.cols as usize]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn as_points(&self) -> impl Iterator<Item = Point> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |x| (0..cols).map(move |y| Point(x, y)))
+    }
+
+    /// A view into this view, with its origin relative to this view.
+    pub fn view(&self, origin: &Point, rows: isize, cols: isize) -> Option<MatrixView<'a, T>> {
+        let fits = origin.0 >= 0
+            && origin.1 >= 0
+            && origin.0 + rows <= self.rows
+            && origin.1 + cols <= self.cols;
+        fits.then(|| MatrixView::new(self.matrix, self.origin + origin, rows, cols))
+            .flatten()
+    }
+}
+
+impl<T: Clone> MatrixView<'_, T> {
+    /// Copy the cells of the view into a grid of their own.
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.iter_rows().flatten().cloned().collect(),
+            addressing: Addressing::Bounded,
+        }
+    }
+}
+
+/// Which neighbours of a cell it is connected to, see [`Matrix::regions_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The cells above, below, left and right.
+    Four,
+    /// The cells above, below, left and right, and the diagonal ones.
+    Eight,
+}
+
+/// A connected region of a [`Matrix`], see [`Matrix::regions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// Index of the region, regions are labelled in the order they are found scanning the grid row by row.
+    pub label: usize,
+    /// The cells of the region, starting with its top left one.
+    pub cells: Vec<Point>,
+    /// Number of cell edges between the region and cells outside of it.
+    pub perimeter: usize,
+    /// Number of straight sides of the region's outline, a side spans neighbouring cell edges facing the same way.
+    pub sides: usize,
+    /// The top left and bottom right corners of the smallest rectangle containing the region.
+    pub bounds: (Point, Point),
+}
+
+impl Region {
+    fn new(label: usize, cells: Vec<Point>, labels: &Matrix<Option<usize>>) -> Region {
+        let inside = |point: Point| labels.get(&point) == Some(&Some(label));
+
+        let mut perimeter = 0;
+        let mut sides = 0;
+
+        for &cell in &cells {
+            for direction in ALL_4_DIRECTIONS {
+                if inside(cell + direction) {
+                    continue;
+                }
+                perimeter += 1;
+
+                // an edge continues the side of the previous cell along the edge, if that cell has the same edge.
+                let along = match direction {
+                    Direction::Up(_) | Direction::Down(_) => LEFT,
+                    Direction::Left(_) | Direction::Right(_) => UP,
+                };
+                let previous = cell + along;
+                if !inside(previous) || inside(previous + direction) {
+                    sides += 1;
+                }
+            }
+        }
+
+        let bounds = cells.iter().fold((cells[0], cells[0]), |(min, max), p| {
+            (
+                Point(min.0.min(p.0), min.1.min(p.1)),
+                Point(max.0.max(p.0), max.1.max(p.1)),
+            )
+        });
+
+        Region {
+            label,
+            cells,
+            perimeter,
+            sides,
+            bounds,
+        }
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Split the grid into regions of neighbouring cells for which `same` holds, e.g. `matrix.regions(|a, b| a == b)`.
+    /// Cells are connected to the cells above, below, left and right of them.
+    pub fn regions(&self, same: impl Fn(&T, &T) -> bool) -> Vec<Region> {
+        self.regions_with(Connectivity::Four, same)
+    }
+
+    /// Like [`regions`](Matrix::regions), with the given connectivity. Perimeters and sides are always measured
+    /// along the edges of the cells, so two cells touching diagonally do not share an edge.
+    pub fn regions_with(
+        &self,
+        connectivity: Connectivity,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> Vec<Region> {
+        let mut labels = Matrix::empty(self.rows as usize, self.cols as usize, None)
+            .with_addressing(self.addressing);
+        let mut regions = Vec::new();
+
+        for start in self.as_points() {
+            if labels.get(&start).expect("Checked").is_some() {
+                continue;
+            }
+
+            let label = regions.len();
+            labels.update(&start, Some(label));
+            let mut cells = vec![start];
+
+            let mut next = 0;
+            while let Some(&point) = cells.get(next) {
+                next += 1;
+                let value = self.get(&point).expect("Checked");
+
+                let neighbors: Vec<(Point, &T)> = match connectivity {
+                    Connectivity::Four => self.neighbors4(&point).collect(),
+                    Connectivity::Eight => self.neighbors8(&point).collect(),
+                };
+
+                for (neighbor, neighbor_value) in neighbors {
+                    if labels.get(&neighbor).expect("Checked").is_none()
+                        && same(value, neighbor_value)
+                    {
+                        labels.update(&neighbor, Some(label));
+                        cells.push(neighbor);
+                    }
+                }
+            }
+
+            regions.push(Region::new(label, cells, &labels));
+        }
+
+        regions
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Point(pub isize, pub isize);
 
@@ -286,3 +755,232 @@ impl AddAssign<Direction> for Point {
         self.1 += other.as_point().1;
     }
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Addressing, Connectivity, Matrix, Point};
+
+    #[test]
+    fn parses_grids() {
+        let matrix = Matrix::parse("#..\n.#.\n").unwrap();
+        assert_eq!((matrix.rows, matrix.cols), (2, 3));
+        assert_eq!(matrix.get(&Point(1, 1)), Some(&b'#'));
+        assert_eq!(matrix.to_string(), "#..\n.#.");
+
+        let digits = Matrix::parse_with("12\n34", |chr| chr - b'0').unwrap();
+        assert_eq!(digits.data, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        let error = Matrix::parse("#..\n.#\n...").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a row as wide as the first one (3 cells)");
+
+        let error = Matrix::parse("#..\n.#..").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        assert!(Matrix::parse("\n").is_err());
+    }
+
+    #[test]
+    fn rejects_windows_line_endings() {
+        let error = Matrix::parse("#..\r\n.#.\r\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert!(error.to_string().contains("Windows line endings"));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let matrix = Matrix::parse("abc\ndef").unwrap();
+
+        let neighbors =
+            |points: Vec<(Point, &u8)>| points.into_iter().map(|(_, chr)| *chr).collect::<Vec<_>>();
+
+        assert_eq!(
+            neighbors(matrix.neighbors4(&Point(0, 0)).collect()),
+            b"db".to_vec()
+        );
+        assert_eq!(
+            neighbors(matrix.neighbors8(&Point(0, 1)).collect()),
+            b"cfeda".to_vec()
+        );
+        assert_eq!(
+            matrix.neighbors4(&Point(1, 2)).next(),
+            Some((Point(0, 2), &b'c'))
+        );
+    }
+
+    #[test]
+    fn wraps_around_on_a_torus() {
+        let mut matrix = Matrix::parse("abc\ndef")
+            .unwrap()
+            .with_addressing(Addressing::Torus);
+
+        assert_eq!(matrix.get(&Point(-1, 0)), Some(&b'd'));
+        assert_eq!(matrix.get(&Point(2, 4)), Some(&b'b'));
+        assert_eq!(matrix.resolve(&Point(-1, -1)), Some(Point(1, 2)));
+
+        *matrix.get_mut(&Point(3, 3)).unwrap() = b'x';
+        assert_eq!(matrix.to_string(), "abc\nxef");
+
+        // up and down wrap onto the same cell with only two rows.
+        let neighbors = matrix.neighbors4(&Point(0, 0)).collect::<Vec<_>>();
+        assert_eq!(
+            neighbors,
+            vec![
+                (Point(1, 0), &b'x'),
+                (Point(0, 2), &b'c'),
+                (Point(0, 1), &b'b'),
+            ]
+        );
+        assert_eq!(matrix.neighbors8(&Point(0, 0)).count(), 5);
+
+        // a single column has no neighbours to the left and right but the point itself.
+        let column = Matrix::parse("a\nb\nc")
+            .unwrap()
+            .with_addressing(Addressing::Torus);
+        let neighbors = column.neighbors4(&Point(0, 0)).collect::<Vec<_>>();
+        assert_eq!(neighbors, vec![(Point(2, 0), &b'c'), (Point(1, 0), &b'b')]);
+
+        let cell = Matrix::parse("a")
+            .unwrap()
+            .with_addressing(Addressing::Torus);
+        assert_eq!(cell.neighbors8(&Point(0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let matrix = Matrix::parse("abc\ndef").unwrap();
+
+        let text = |cells: Vec<&u8>| {
+            cells
+                .into_iter()
+                .map(|chr| *chr as char)
+                .collect::<String>()
+        };
+
+        assert_eq!(matrix.row(1), b"def");
+        assert_eq!(matrix.iter_rows().collect::<Vec<_>>(), vec![b"abc", b"def"]);
+        assert_eq!(text(matrix.column(1).collect()), "be");
+        assert_eq!(
+            matrix
+                .iter_columns()
+                .map(|col| text(col.collect()))
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(
+            matrix
+                .diagonals()
+                .map(|d| text(d.collect()))
+                .collect::<Vec<_>>(),
+            vec!["c", "bf", "ae", "d"]
+        );
+        assert_eq!(
+            matrix
+                .anti_diagonals()
+                .map(|d| text(d.collect()))
+                .collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"]
+        );
+        assert_eq!(
+            matrix.ray(&Point(1, 2), Point(0, -1)).collect::<Vec<_>>(),
+            vec![
+                (Point(1, 2), &b'f'),
+                (Point(1, 1), &b'e'),
+                (Point(1, 0), &b'd')
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of a grid with 3 columns")]
+    fn rejects_columns_outside_of_the_grid() {
+        let matrix = Matrix::parse("abc\ndef").unwrap();
+        let _ = matrix.column(3);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let matrix = Matrix::parse("abc\ndef").unwrap();
+
+        assert_eq!(matrix.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(matrix.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(matrix.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(matrix.flip_h().to_string(), "cba\nfed");
+        assert_eq!(matrix.flip_v().to_string(), "def\nabc");
+        assert_eq!(
+            matrix.rotate_cw().rotate_cw().to_string(),
+            matrix.flip_h().flip_v().to_string()
+        );
+    }
+
+    #[test]
+    fn views_parts_of_grids() {
+        let matrix = Matrix::parse("abcd\nefgh\nijkl").unwrap();
+
+        let view = matrix.view(&Point(1, 1), 2, 3).unwrap();
+        assert_eq!((view.rows, view.cols), (2, 3));
+        assert_eq!(view.get(&Point(0, 0)), Some(&b'f'));
+        assert_eq!(view.get(&Point(1, 2)), Some(&b'l'));
+        assert_eq!(view.get(&Point(2, 0)), None);
+        assert_eq!(view.row(1), b"jkl");
+        assert_eq!(view.to_matrix().to_string(), "fgh\njkl");
+
+        let inner = view.view(&Point(1, 1), 1, 2).unwrap();
+        assert_eq!(inner.to_matrix().to_string(), "kl");
+
+        assert!(matrix.view(&Point(1, 1), 3, 1).is_none());
+        assert!(view.view(&Point(-1, 0), 1, 1).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside of a view with 2 rows")]
+    fn rejects_rows_outside_of_views() {
+        let matrix = Matrix::parse("abcd\nefgh\nijkl").unwrap();
+        let view = matrix.view(&Point(0, 1), 2, 3).unwrap();
+        view.row(2);
+    }
+
+    #[test]
+    fn finds_regions() {
+        let matrix = Matrix::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let regions = matrix.regions(|a, b| a == b);
+
+        let measures = regions
+            .iter()
+            .map(|r| (r.label, r.area(), r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            measures,
+            vec![
+                (0, 4, 10, 4),
+                (1, 4, 8, 4),
+                (2, 4, 10, 8),
+                (3, 1, 4, 4),
+                (4, 3, 8, 4)
+            ]
+        );
+        assert_eq!(regions[2].bounds, (Point(1, 2), Point(3, 3)));
+        assert_eq!(regions[2].cells[0], Point(1, 2));
+
+        // the grid is left untouched.
+        assert_eq!(matrix.to_string(), "AAAA\nBBCD\nBBCC\nEEEC");
+    }
+
+    #[test]
+    fn finds_regions_with_diagonal_neighbors() {
+        let matrix = Matrix::parse("X.\n.X").unwrap();
+
+        assert_eq!(matrix.regions(|a, b| a == b).len(), 4);
+
+        let regions = matrix.regions_with(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].cells, vec![Point(0, 0), Point(1, 1)]);
+        assert_eq!((regions[0].perimeter, regions[0].sides), (8, 8));
+        assert_eq!(regions[0].bounds, (Point(0, 0), Point(1, 1)));
+    }
+}
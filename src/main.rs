@@ -1,81 +1,207 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, verify};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::Puzzle;
 #[cfg(feature = "today")]
 use std::process;
 
+/// All solutions of `src/bin`, compiled into this binary. See `build.rs`.
+#[cfg(feature = "registry")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::time::{CompareOptions, TimeOptions};
+    use advent_of_code::template::fuzz::FuzzConfig;
+    use advent_of_code::template::stats::{parse_bench_time, parse_count, BenchConfig};
+    use advent_of_code::template::{Day, Puzzle, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Option<Day>,
             all: bool,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
-            overwrite: bool,
+            force: bool,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            examples: bool,
+            fuzz: Option<FuzzConfig>,
+            watch: bool,
+            tests: bool,
         },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
         },
         Time {
-            all: bool,
+            year: Year,
             day: Option<Day>,
-            store: bool,
+            options: TimeOptions,
+            history: Option<Day>,
+        },
+        Verify {
+            year: Year,
+            days: Vec<Day>,
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parse `--jobs <n>` / `-j <n>`. Defaults to running one day at a time.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, pico_args::Error> {
+        Ok(args
+            .opt_value_from_fn(["-j", "--jobs"], |s| parse_count(s, "--jobs"))?
+            .map_or(1, |jobs| jobs as usize))
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // NOTE: every command accepts `--year`, defaulting to `AOC_YEAR`.
+        let year: Year = args.opt_value_from_str("--year")?.unwrap_or_default();
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                jobs: parse_jobs(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let bench = BenchConfig {
+                    budget: args
+                        .opt_value_from_fn("--bench-time", parse_bench_time)?
+                        .unwrap_or(BenchConfig::default().budget),
+                    iterations: args
+                        .opt_value_from_fn("--iterations", |s| parse_count(s, "--iterations"))?,
+                    warmup: args.opt_value_from_str("--warmup")?,
+                };
+                let stat = args.opt_value_from_str("--stat")?.unwrap_or_default();
+                // NOTE: `--fail-on-regression` implies `--compare`.
+                let fail_on_regression = args.opt_value_from_str("--fail-on-regression")?;
+                let compare = (args.contains("--compare") || fail_on_regression.is_some())
+                    .then_some(CompareOptions { fail_on_regression });
+                let history = args.opt_value_from_str("--history")?;
+                // NOTE: benchmarks run serially unless requested otherwise, so timings stay comparable.
+                let jobs = parse_jobs(&mut args)?;
 
                 AppArguments::Time {
-                    all,
+                    year,
                     day: args.opt_free_from_str()?,
-                    store,
+                    options: TimeOptions {
+                        run_all: all,
+                        store,
+                        bench,
+                        statistic: stat,
+                        compare,
+                        jobs,
+                    },
+                    history,
+                }
+            }
+            Some("verify") => {
+                let release = args.contains("--release");
+
+                let mut days = vec![];
+                while let Some(day) = args.opt_free_from_str()? {
+                    days.push(day);
+                }
+
+                AppArguments::Verify {
+                    year,
+                    days,
+                    release,
                 }
             }
             Some("download") => AppArguments::Download {
+                year,
                 all: args.contains("--all"),
                 day: args.opt_free_from_str()?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                puzzle: Puzzle::new(year, args.free_from_str()?),
             },
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                // NOTE: `--overwrite` is kept as an alias of `--force`.
+                let force = args.contains("--force") || args.contains("--overwrite");
+
+                AppArguments::Scaffold {
+                    puzzle: Puzzle::new(year, args.free_from_str()?),
+                    download,
+                    force,
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let examples = args.contains("--examples");
+                let watch = args.contains("--watch");
+                let tests = args.contains("--tests");
+                let cases = args.opt_value_from_fn("--cases", |s| parse_count(s, "--cases"))?;
+                let seed = args.opt_value_from_str("--seed")?;
+                // NOTE: `--cases` and `--seed` imply `--fuzz`.
+                let fuzz =
+                    (args.contains("--fuzz") || cases.is_some() || seed.is_some()).then(|| {
+                        let default = FuzzConfig::default();
+                        FuzzConfig {
+                            cases: cases.unwrap_or(default.cases),
+                            seed: seed.unwrap_or(default.seed),
+                        }
+                    });
+
+                if examples && submit.is_some() {
+                    eprintln!("`--examples` can not be combined with `--submit`.");
+                    process::exit(1);
+                }
+
+                if watch && (examples || dhat || submit.is_some()) {
+                    eprintln!(
+                        "`--watch` can not be combined with `--examples`, `--dhat` or `--submit`."
+                    );
+                    process::exit(1);
+                }
+
+                if fuzz.is_some() && (examples || watch || dhat || submit.is_some()) {
+                    eprintln!("`--fuzz` can not be combined with `--examples`, `--watch`, `--dhat` or `--submit`.");
+                    process::exit(1);
+                }
+
+                if tests && !watch {
+                    eprintln!("`--tests` requires `--watch`.");
+                    process::exit(1);
+                }
+
+                AppArguments::Solve {
+                    puzzle: Puzzle::new(year, args.free_from_str()?),
+                    release,
+                    submit,
+                    dhat,
+                    examples,
+                    fuzz,
+                    watch,
+                    tests,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -98,39 +224,69 @@ mod args {
 }
 
 fn main() {
+    #[cfg(feature = "registry")]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day, all } => download::handle(day, all),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+            } => all::handle(year, release, jobs),
+            AppArguments::Time {
+                year,
                 day,
+                options,
+                history,
+            } => match history {
+                Some(day) => time::history(year, day, options.statistic),
+                None => time::handle(year, day, options),
+            },
+            AppArguments::Verify {
+                year,
+                days,
+                release,
+            } => verify::handle(year, &days, release),
+            AppArguments::Download { year, day, all } => download::handle(year, day, all),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
-                overwrite,
+                force,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, force);
                 if download {
-                    download::handle(Some(day), false);
+                    download::handle(puzzle.year, Some(puzzle.day), false);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                examples,
+                fuzz,
+                watch,
+                tests,
+            } => {
+                if watch {
+                    solve::handle_watch(puzzle, release, tests);
+                } else {
+                    solve::handle(puzzle, release, dhat, submit, examples, fuzz);
+                }
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day, false);
-                        read::handle(day)
+                match Puzzle::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle.year, Some(puzzle.day), false);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
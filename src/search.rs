@@ -0,0 +1,311 @@
+/// Searches for cheapest paths over graphs with nodes of any kind, e.g. a position on a [`Matrix`] and a facing.
+///
+/// The searches keep every predecessor of a node on a cheapest path to it. Besides one cheapest path, [`Paths`]
+/// returns all of them, or the nodes on any of them. [`Matrix::shortest_paths`] covers the common case of walking
+/// between cells of a grid.
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{Matrix, Point};
+
+/// Find the paths with the fewest steps from `start` to a node for which `success` holds.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<Paths<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    dijkstra(
+        start,
+        |node| successors(node).into_iter().map(|next| (next, 1)),
+        success,
+    )
+}
+
+/// Find the cheapest paths from `start` to a node for which `success` holds.
+/// `successors` returns the nodes reachable from a node, together with the cost of the step.
+pub fn dijkstra<N, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<Paths<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(start, successors, |_| 0, success)
+}
+
+/// Like [`dijkstra`], guided by a `heuristic` that estimates the remaining cost from a node to a goal.
+/// To find every cheapest path, the estimate must never be more than the actual cost, and never drop by more
+/// than the cost of a step, like the manhattan distance on a grid.
+pub fn astar<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Paths<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut paths = Paths {
+        index: HashMap::from([(start.clone(), 0)]),
+        nodes: vec![start],
+        costs: vec![0],
+        predecessors: vec![vec![]],
+        goals: vec![],
+        cost: 0,
+    };
+    let mut closed = vec![false];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&paths.nodes[0]), 0, 0))]);
+    let mut best = None;
+
+    while let Some(Reverse((estimate, cost, id))) = queue.pop() {
+        // nodes estimated as cheap as the best goal can still be on a cheapest path.
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        if closed[id] || cost > paths.costs[id] {
+            continue;
+        }
+        closed[id] = true;
+
+        let node = paths.nodes[id].clone();
+        if success(&node) {
+            best = Some(cost);
+            paths.goals.push(id);
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let next_id = match paths.index.entry(next) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    let next_id = paths.nodes.len();
+                    paths.nodes.push(entry.key().clone());
+                    paths.costs.push(u64::MAX);
+                    paths.predecessors.push(vec![]);
+                    closed.push(false);
+                    entry.insert(next_id);
+                    next_id
+                }
+            };
+
+            if next_cost < paths.costs[next_id] {
+                paths.costs[next_id] = next_cost;
+                paths.predecessors[next_id] = vec![id];
+                let estimate = next_cost + heuristic(&paths.nodes[next_id]);
+                queue.push(Reverse((estimate, next_cost, next_id)));
+            } else if next_cost == paths.costs[next_id] {
+                paths.predecessors[next_id].push(id);
+            }
+        }
+    }
+
+    best.map(|cost| Paths { cost, ..paths })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The cheapest paths found by a search, from its start to every goal that is reached at the lowest cost.
+pub struct Paths<N> {
+    /// Nodes are stored once and referred to by their position in `nodes`.
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    costs: Vec<u64>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+    cost: u64,
+}
+
+impl<N: Clone + Eq + Hash> Paths<N> {
+    /// Cost of the cheapest paths.
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    /// The goals at the end of the cheapest paths, more than one if several goals are as cheap to reach.
+    pub fn goals(&self) -> impl Iterator<Item = &N> {
+        self.goals.iter().map(|&id| &self.nodes[id])
+    }
+
+    /// The nodes preceding a node on cheapest paths to it.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.index
+            .get(node)
+            .into_iter()
+            .flat_map(|&id| &self.predecessors[id])
+            .map(|&id| &self.nodes[id])
+    }
+
+    /// One of the cheapest paths, from the start to a goal.
+    pub fn path(&self) -> Vec<N> {
+        let mut path = vec![self.nodes[self.goals[0]].clone()];
+        let mut id = self.goals[0];
+
+        while let Some(&previous) = self.predecessors[id].first() {
+            path.push(self.nodes[previous].clone());
+            id = previous;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Every cheapest path, from the start to a goal. Their number can grow exponentially with the size of the graph,
+    /// [`nodes_on_paths`](Paths::nodes_on_paths) is usually enough.
+    pub fn all_paths(&self) -> Vec<Vec<N>> {
+        let mut paths = vec![];
+        for &goal in &self.goals {
+            self.collect_paths(goal, &mut vec![], &mut paths);
+        }
+        paths
+    }
+
+    fn collect_paths(&self, id: usize, suffix: &mut Vec<usize>, paths: &mut Vec<Vec<N>>) {
+        suffix.push(id);
+
+        if self.predecessors[id].is_empty() {
+            paths.push(
+                suffix
+                    .iter()
+                    .rev()
+                    .map(|&id| self.nodes[id].clone())
+                    .collect(),
+            );
+        }
+        for &previous in &self.predecessors[id] {
+            self.collect_paths(previous, suffix, paths);
+        }
+
+        suffix.pop();
+    }
+
+    /// The nodes on any of the cheapest paths, including the start and the goals.
+    pub fn nodes_on_paths(&self) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut stack = self.goals.clone();
+
+        while let Some(id) = stack.pop() {
+            if seen.insert(id) {
+                stack.extend(&self.predecessors[id]);
+            }
+        }
+
+        seen.into_iter().map(|id| self.nodes[id].clone()).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T> Matrix<T> {
+    /// The paths with the fewest steps between two cells, stepping up, down, left and right onto cells for which
+    /// `passable` holds. Nodes that carry more than a position, e.g. a facing, can be searched with [`dijkstra`].
+    pub fn shortest_paths(
+        &self,
+        start: &Point,
+        goal: &Point,
+        passable: impl Fn(&T) -> bool,
+    ) -> Option<Paths<Point>> {
+        let passable = &passable;
+        bfs(
+            *start,
+            |point| {
+                self.neighbors4(point)
+                    .filter(move |(_, value)| passable(value))
+                    .map(|(next, _)| next)
+            },
+            |point| point == goal,
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{astar, dijkstra};
+    use crate::{Matrix, Point};
+
+    /// Two routes of the same cost from `a` to `d`, and a more expensive direct one.
+    fn diamond(node: &char) -> Vec<(char, u64)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 2), ('d', 5)],
+            'b' => vec![('d', 3)],
+            'c' => vec![('d', 2)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_all_cheapest_paths() {
+        let paths = dijkstra('a', diamond, |node| *node == 'd').unwrap();
+
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.goals().collect::<Vec<_>>(), vec![&'d']);
+        assert_eq!(paths.path().len(), 3);
+        assert_eq!(
+            paths.predecessors(&'d').copied().collect::<HashSet<_>>(),
+            HashSet::from(['b', 'c'])
+        );
+
+        let mut all = paths.all_paths();
+        all.sort();
+        assert_eq!(all, vec![vec!['a', 'b', 'd'], vec!['a', 'c', 'd']]);
+        assert_eq!(paths.nodes_on_paths(), HashSet::from(['a', 'b', 'c', 'd']));
+
+        assert!(dijkstra('b', diamond, |node| *node == 'a').is_none());
+    }
+
+    #[test]
+    fn searches_with_node_state() {
+        // walk to 10, where steps of 2 are cheaper once two steps of 1 have been taken.
+        let successors = |&(position, ones): &(u64, u64)| {
+            let mut next = vec![((position + 1, ones + 1), 2)];
+            if ones >= 2 {
+                next.push(((position + 2, ones), 1));
+            }
+            next.into_iter()
+                .filter(|((position, _), _)| *position <= 10)
+        };
+
+        let paths = astar(
+            (0, 0),
+            successors,
+            |(position, _)| (10 - position) / 2,
+            |(position, _)| *position == 10,
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost(), 8);
+        assert_eq!(paths.goals().collect::<Vec<_>>(), vec![&(10, 2)]);
+    }
+
+    #[test]
+    fn finds_shortest_paths_on_grids() {
+        let matrix = Matrix::parse("S..\n.#.\n..E").unwrap();
+
+        let paths = matrix
+            .shortest_paths(&Point(0, 0), &Point(2, 2), |chr| *chr != b'#')
+            .unwrap();
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.all_paths().len(), 2);
+        assert_eq!(paths.nodes_on_paths().len(), 8);
+        assert_eq!(paths.path().first(), Some(&Point(0, 0)));
+        assert_eq!(paths.path().last(), Some(&Point(2, 2)));
+
+        assert!(matrix
+            .shortest_paths(&Point(0, 0), &Point(1, 1), |chr| *chr != b'#')
+            .is_none());
+    }
+}
@@ -0,0 +1,234 @@
+/// Heap-allocation statistics of solution parts.
+///
+/// With the `alloc-stats` feature, [`CountingAlloc`] is installed as the global allocator. It wraps the system allocator
+/// and counts allocations, allocated bytes and the peak heap size. The runner measures the first (cold) run of every part.
+/// Counting is not available together with `dhat-heap`, which installs its own allocator.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tinyjson::JsonValue;
+
+/// Whether allocations are counted in this build.
+pub const ENABLED: bool = cfg!(all(feature = "alloc-stats", not(feature = "dhat-heap")));
+
+#[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static HEAP_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_HEAP_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts allocations of the system allocator.
+/// Reallocations count as an allocation of the new size.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let heap = HEAP_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK_HEAP_BYTES.fetch_max(heap, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        HEAP_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Allocations made while running a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Sum of the sizes of all allocations.
+    pub bytes: u64,
+    /// Most bytes held at once, on top of what was allocated before the part ran.
+    pub peak_bytes: u64,
+}
+
+impl AllocStats {
+    /// Allocations of the parse step and both parts of a day: allocations and bytes add up, the peak is the highest one.
+    pub fn combine(stats: impl IntoIterator<Item = AllocStats>) -> Option<AllocStats> {
+        stats.into_iter().reduce(|a, b| AllocStats {
+            allocations: a.allocations + b.allocations,
+            bytes: a.bytes + b.bytes,
+            peak_bytes: a.peak_bytes.max(b.peak_bytes),
+        })
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Run a function and count its allocations. Returns [`None`] for the statistics if allocations are not counted.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !ENABLED {
+        return (func(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let heap = HEAP_BYTES.load(Ordering::Relaxed);
+    PEAK_HEAP_BYTES.store(heap, Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: PEAK_HEAP_BYTES.load(Ordering::Relaxed).saturating_sub(heap),
+    };
+
+    (result, Some(stats))
+}
+
+/// Format a number of bytes with binary units, e.g. `512 B` or `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: number("allocations")? as u64,
+            bytes: number("bytes")? as u64,
+            peak_bytes: number("peak_bytes")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn combines_stats() {
+        let stats = AllocStats::combine([
+            AllocStats {
+                allocations: 2,
+                bytes: 100,
+                peak_bytes: 80,
+            },
+            AllocStats {
+                allocations: 3,
+                bytes: 50,
+                peak_bytes: 50,
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 5,
+                bytes: 150,
+                peak_bytes: 80,
+            }
+        );
+        assert_eq!(stats.to_string(), "5 allocs, 150 B, peak 80 B");
+        assert_eq!(AllocStats::try_from(&JsonValue::from(&stats)), Ok(stats));
+    }
+}
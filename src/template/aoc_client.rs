@@ -0,0 +1,621 @@
+/// Minimal Advent of Code HTTP client, used to read puzzles, download inputs and submit answers.
+///
+/// Requests are authenticated with the `session` cookie of the website. It is read from the `AOC_SESSION`
+/// environment variable, from the file in `AOC_SESSION_FILE` or from `<home_directory>/.adventofcode.session`.
+/// The base URL can be changed with `AOC_BASE_URL`, e.g. to test against a local server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, str::FromStr, time::Duration};
+
+use crate::template::Puzzle;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the client to the Advent of Code maintainers, as requested in their automation guidelines.
+const DEFAULT_USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/fspoettel/advent-of-code-rust)"
+);
+
+const MAX_REDIRECTS: usize = 5;
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found.
+    MissingSession,
+    /// The session cookie was rejected or has expired.
+    Unauthorized,
+    /// The puzzle does not exist or has not been unlocked yet.
+    NotFound,
+    RateLimited,
+    BadStatus(u16),
+    TooManyRedirects,
+    /// A redirect pointed to another host, which is not sent the session cookie.
+    ForeignRedirect(String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create \"<home_directory>/.adventofcode.session\"."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie is invalid or has expired.")
+            }
+            AocClientError::NotFound => {
+                write!(f, "the puzzle does not exist or has not been unlocked yet.")
+            }
+            AocClientError::RateLimited => write!(f, "too many requests, try again later."),
+            AocClientError::BadStatus(status) => {
+                write!(f, "unexpected response status {status}.")
+            }
+            AocClientError::TooManyRedirects => write!(f, "too many redirects."),
+            AocClientError::ForeignRedirect(location) => {
+                write!(f, "refused to follow a redirect to another host: {location}")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// Verdict of an answer submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint.
+    Incorrect,
+    /// An answer was submitted too recently, the submission has not been checked.
+    TooRecent,
+    /// The part has already been solved or is locked.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Derive the verdict from the message returned by the website.
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Verdict::TooHigh
+            } else if message.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooRecent => "too_recent",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too_recent" => Ok(Verdict::TooRecent),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// Response to an answer submission.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// Message shown by the website, converted to text.
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, user_agent: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(user_agent)
+            .timeout(Duration::from_secs(30))
+            // NOTE: redirects are followed manually, so that redirects to the login page can be detected.
+            .redirects(0)
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Create a client from the environment. See the module documentation.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let user_agent = env::var("AOC_USER_AGENT").unwrap_or_else(|_| DEFAULT_USER_AGENT.into());
+        Ok(Self::new(&base_url, &session, &user_agent))
+    }
+
+    /// Fetch the personal puzzle input.
+    pub fn input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        self.request(&puzzle_url(puzzle, "/input"), None)
+    }
+
+    /// Fetch the puzzle description, converted to markdown. Contains part two once part one is solved.
+    pub fn puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let html = self.request(&puzzle_url(puzzle, ""), None)?;
+        Ok(articles(&html)
+            .iter()
+            .map(|article| html_to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Submit the answer of a part.
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let level = part.to_string();
+        let html = self.request(
+            &puzzle_url(puzzle, "/answer"),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+
+        let message = articles(&html)
+            .first()
+            .map(|article| html_to_markdown(article))
+            .unwrap_or_default();
+
+        Ok(Submission {
+            verdict: Verdict::from_message(&message),
+            message,
+        })
+    }
+
+    /// Send a request to a path of the base URL. Requests with a form are sent as POST.
+    fn request(&self, path: &str, form: Option<&[(&str, &str)]>) -> Result<String, AocClientError> {
+        let cookie = format!("session={}", self.session);
+        let mut url = format!("{}{path}", self.base_url);
+        let mut form = form;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let request = match form {
+                Some(_) => self.agent.post(&url),
+                None => self.agent.get(&url),
+            }
+            .set("Cookie", &cookie);
+
+            let result = match form {
+                Some(form) => request.send_form(form),
+                None => request.call(),
+            };
+
+            let response = match result {
+                Ok(response) => response,
+                Err(ureq::Error::Status(status, _)) => return Err(status_error(status)),
+                Err(ureq::Error::Transport(e)) => {
+                    return Err(AocClientError::Transport(e.to_string()))
+                }
+            };
+
+            match response.status() {
+                200..=299 => return Ok(response.into_string()?),
+                300..=399 => {
+                    let location = response
+                        .header("Location")
+                        .ok_or(AocClientError::BadStatus(response.status()))?;
+
+                    // the website redirects to the login page if the session is not valid.
+                    if location.contains("/auth/login") {
+                        return Err(AocClientError::Unauthorized);
+                    }
+
+                    url = if location.starts_with('/') {
+                        format!("{}{location}", self.base_url)
+                    } else if origin(location) == origin(&self.base_url) {
+                        location.to_string()
+                    } else {
+                        // NOTE: every request carries the session cookie, it must not leave the website.
+                        return Err(AocClientError::ForeignRedirect(location.to_string()));
+                    };
+                    // redirected requests are sent as GET.
+                    form = None;
+                }
+                status => return Err(status_error(status)),
+            }
+        }
+
+        Err(AocClientError::TooManyRedirects)
+    }
+}
+
+fn puzzle_url(puzzle: Puzzle, suffix: &str) -> String {
+    format!(
+        "/{}/day/{}{suffix}",
+        puzzle.year.into_inner(),
+        puzzle.day.into_inner()
+    )
+}
+
+/// Scheme, host and port of a URL, e.g. `https://adventofcode.com` for `https://adventofcode.com/2024/day/1`.
+fn origin(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", url));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    format!("{}://{}", scheme, authority).to_ascii_lowercase()
+}
+
+fn status_error(status: u16) -> AocClientError {
+    match status {
+        401 | 403 => AocClientError::Unauthorized,
+        404 => AocClientError::NotFound,
+        429 => AocClientError::RateLimited,
+        _ => AocClientError::BadStatus(status),
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let path = env::var("AOC_SESSION_FILE")
+        .map(PathBuf::from)
+        .or_else(|_| {
+            env::var("HOME")
+                .or_else(|_| env::var("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+        });
+
+    path.ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Inner HTML of all `<article>` elements of a page.
+fn articles(html: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..]
+            .find("</article>")
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+        result.push(&rest[content_start..end]);
+        rest = &rest[end..];
+    }
+
+    result
+}
+
+/// Convert the subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            out.push_str(&decode_entities(rest));
+            break;
+        };
+
+        out.push_str(&decode_entities(&rest[..tag_start]));
+
+        let Some(tag_end) = rest[tag_start..].find('>').map(|i| tag_start + i) else {
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_end];
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2", true) | ("p", true) | ("ul", true) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("- "),
+            ("li", true) | ("br", false) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) => out.push('*'),
+            _ => {}
+        }
+
+        rest = &rest[tag_end + 1..];
+    }
+
+    // collapse the blank lines between blocks.
+    let mut result = out.trim().to_string();
+    while result.contains("\n\n\n") {
+        result = result.replace("\n\n\n", "\n\n");
+    }
+    result
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::{html_to_markdown, origin, AocClient, AocClientError, Verdict};
+    use crate::{day, template::Puzzle, year};
+
+    /// Serve the given raw HTTP responses on a local port, one per connection.
+    /// Sends every received request (head and body) through the returned channel.
+    fn mock_server(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                tx.send(request).unwrap();
+
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (base_url, rx)
+    }
+
+    fn response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn puzzle() -> Puzzle {
+        Puzzle::new(year!(2024), day!(1))
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, requests) = mock_server(vec![response("200 OK", "", "1 2\n3 4\n")]);
+        let client = AocClient::new(&base_url, "abc\n", "test-agent");
+
+        assert_eq!(client.input(puzzle()).unwrap(), "1 2\n3 4\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.contains("User-Agent: test-agent\r\n"));
+    }
+
+    #[test]
+    fn maps_status_codes() {
+        let (base_url, _requests) = mock_server(vec![
+            response("400 Bad Request", "", "Puzzle inputs differ by user."),
+            response("403 Forbidden", "", ""),
+            response("404 Not Found", "", ""),
+            response("500 Internal Server Error", "", ""),
+            response("302 Found", "Location: /auth/login\r\n", ""),
+        ]);
+        let client = AocClient::new(&base_url, "abc", "test-agent");
+
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::BadStatus(400))
+        ));
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::Unauthorized)
+        ));
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::NotFound)
+        ));
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::BadStatus(500))
+        ));
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn follows_redirects() {
+        let (base_url, requests) = mock_server(vec![
+            response("301 Moved Permanently", "Location: /2024/day/1\r\n", ""),
+            response(
+                "200 OK",
+                "",
+                "<main><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2><p>Hello</p></article></main>",
+            ),
+        ]);
+        let client = AocClient::new(&base_url, "abc", "test-agent");
+
+        assert_eq!(
+            client.puzzle(puzzle()).unwrap(),
+            "## --- Day 1: Test ---\n\nHello"
+        );
+        assert!(requests.recv().unwrap().starts_with("GET /2024/day/1 "));
+        assert!(requests.recv().unwrap().starts_with("GET /2024/day/1 "));
+    }
+
+    #[test]
+    fn keeps_the_session_on_the_website() {
+        let (base_url, requests) = mock_server(vec![response(
+            "302 Found",
+            "Location: https://example.com/2024/day/1\r\n",
+            "",
+        )]);
+        let client = AocClient::new(&base_url, "abc", "test-agent");
+
+        assert!(matches!(
+            client.input(puzzle()),
+            Err(AocClientError::ForeignRedirect(location)) if location == "https://example.com/2024/day/1"
+        ));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /2024/day/1/input "));
+        assert!(requests.try_recv().is_err());
+
+        assert_eq!(origin(&base_url), origin(&format!("{base_url}/2024/day/1")));
+        assert_eq!(
+            origin("https://AdventOfCode.com/2024?x"),
+            "https://adventofcode.com"
+        );
+        assert_ne!(
+            origin("https://adventofcode.com.evil.test/"),
+            "https://adventofcode.com"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, requests) = mock_server(vec![response(
+            "200 OK",
+            "",
+            "<article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article>",
+        )]);
+        let client = AocClient::new(&base_url, "abc", "test-agent");
+
+        let submission = client.submit(puzzle(), 2, "42 & more").unwrap();
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert!(submission
+            .message
+            .starts_with("That's not the right answer"));
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42+%26+more"));
+    }
+
+    #[test]
+    fn converts_descriptions() {
+        let html = "<h2>--- Day 1 ---</h2><p>Find the <em>sum</em> of <code>a &lt; b</code>:</p>\n<pre><code>1 2\n3 4\n</code></pre>\n<ul><li>one</li><li>two</li></ul>";
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1 ---\n\nFind the *sum* of `a < b`:\n\n```\n1 2\n3 4\n```\n\n- one\n- two"
+        );
+    }
+
+    #[test]
+    fn derives_verdicts() {
+        assert_eq!(
+            Verdict::from_message("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_message("You gave an answer too recently; you have to wait."),
+            Verdict::TooRecent
+        );
+        assert_eq!(
+            Verdict::from_message("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, run_multi::run_multi, Puzzle, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(year: Year, is_release: bool, jobs: usize) {
+    let puzzles = all_days().map(|day| Puzzle::new(year, day)).collect();
+    run_multi(&puzzles, is_release, None, jobs);
 }
@@ -1,26 +1,57 @@
-use crate::template::{all_days, aoc_cli, Day};
-use std::process;
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Option<Day>, all: bool) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::{all_days, Day, Puzzle, Year};
 
-    if all {
-        for day in all_days() {
-            if let Err(e) = aoc_cli::download(day) {
-                eprintln!("failed to call aoc-cli: {e}");
-                process::exit(1);
-            }
-        }
+pub fn handle(year: Year, day: Option<Day>, all: bool) {
+    let days: Vec<Day> = if all {
+        all_days().collect()
     } else if let Some(day) = day {
-        if let Err(e) = aoc_cli::download(day) {
-            eprintln!("failed to call aoc-cli: {e}");
-            process::exit(1);
-        };
+        vec![day]
     } else {
         eprintln!("Choose a day or --all.");
         process::exit(1);
+    };
+
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to create Advent of Code client: {e}");
+            process::exit(1);
+        }
+    };
+
+    for day in days {
+        if let Err(e) = download(&client, Puzzle::new(year, day)) {
+            eprintln!("Failed to download day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn download(client: &AocClient, puzzle: Puzzle) -> Result<(), AocClientError> {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let input = client.input(puzzle)?;
+    let description = client.puzzle(puzzle)?;
+
+    // the year-scoped layout might not have been scaffolded yet.
+    for (path, content) in [(&input_path, input), (&puzzle_path, description)] {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, content)?;
     }
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
 }
@@ -1,15 +1,24 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::AocClient;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: Puzzle) {
+    let description = AocClient::from_env().and_then(|client| client.puzzle(puzzle));
+
+    match description {
+        Ok(description) => {
+            println!("{description}");
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+            // keep the local copy of the description up to date, e.g. after part one was solved.
+            let path = puzzle.data_path("puzzles", "md");
+            if path.parent().is_some_and(|dir| dir.exists()) {
+                let _ = fs::write(path, description);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to read puzzle: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -1,90 +1,154 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::template::Day;
+use crate::template::Puzzle;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
+/// A file created by the scaffold command.
+struct ScaffoldFile {
+    description: &'static str,
+    path: PathBuf,
+    content: String,
+}
+
+/// What happens to a scaffold file.
+enum Action {
+    Create,
+    /// The file exists and is left alone.
+    Skip,
+    /// The file exists and is replaced, after moving it to the backup path.
+    Replace {
+        backup: PathBuf,
+    },
+}
+
+fn render_module(puzzle: Puzzle) -> String {
+    let day_number = puzzle.day.into_inner().to_string();
+    let (solution_args, puzzle_const) = if puzzle.is_year_scoped() {
+        (format!("year = {}, {day_number}", puzzle.year), "PUZZLE")
     } else {
-        file.create_new(true);
+        (day_number, "DAY")
+    };
+
+    MODULE_TEMPLATE
+        .replace("%SOLUTION_ARGS%", &solution_args)
+        .replace("%PUZZLE_CONST%", puzzle_const)
+}
+
+/// Backups are suffixed with the current timestamp, so earlier backups are never replaced.
+fn backup_path(path: &Path) -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".{timestamp}.bak"));
+    PathBuf::from(backup)
+}
+
+fn plan(file: &ScaffoldFile, force: bool) -> Action {
+    if !file.path.exists() {
+        Action::Create
+    } else if force {
+        Action::Replace {
+            backup: backup_path(&file.path),
+        }
+    } else {
+        Action::Skip
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn apply(file: &ScaffoldFile, action: &Action) -> Result<(), std::io::Error> {
+    match action {
+        Action::Skip => return Ok(()),
+        Action::Replace { backup } => fs::rename(&file.path, backup)?,
+        Action::Create => {}
+    }
+
+    if let Some(dir) = file.path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // NOTE: never truncate, a file that appeared in the meantime is an error.
     OpenOptions::new()
         .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+        .create_new(true)
+        .open(&file.path)?
+        .write_all(file.content.as_bytes())
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let output_path = format!("data/outputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffold the module and data files of a puzzle. Existing files are left alone,
+/// unless `force` is set, in which case they are backed up and replaced.
+pub fn handle(puzzle: Puzzle, force: bool) {
+    let files = [
+        ScaffoldFile {
+            description: "module file",
+            path: PathBuf::from(puzzle.bin_path()),
+            content: render_module(puzzle),
+        },
+        ScaffoldFile {
+            description: "empty input file",
+            path: puzzle.data_path("inputs", "txt"),
+            content: String::new(),
+        },
+        ScaffoldFile {
+            description: "empty example file",
+            path: puzzle.data_path("examples", "txt"),
+            content: String::new(),
+        },
+        ScaffoldFile {
+            description: "empty output file",
+            path: puzzle.data_path("outputs", "txt"),
+            content: String::new(),
+        },
+    ];
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    let actions: Vec<Action> = files.iter().map(|file| plan(file, force)).collect();
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    for (file, action) in files.iter().zip(&actions) {
+        let path = file.path.display();
+        match action {
+            Action::Create => println!("Create {} \"{path}\"", file.description),
+            Action::Skip => println!("Skip existing {} \"{path}\"", file.description),
+            Action::Replace { backup } => println!(
+                "Replace {} \"{path}\", backing it up to \"{}\"",
+                file.description,
+                backup.display()
+            ),
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    for (file, action) in files.iter().zip(&actions) {
+        if let Err(e) = apply(file, action) {
+            eprintln!(
+                "Failed to create {} \"{}\": {e}",
+                file.description,
+                file.path.display()
+            );
             process::exit(1);
         }
     }
 
-    match create_file(&output_path) {
-        Ok(_) => {
-            println!("Created empty output file \"{}\"", &output_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create output file: {e}");
-            process::exit(1);
-        }
+    if actions.iter().any(|action| matches!(action, Action::Skip)) {
+        println!(
+            "Existing files were not changed. Pass `--force` to back them up and replace them."
+        );
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if puzzle.is_year_scoped() {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    } else {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    }
 }
@@ -1,9 +1,40 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::fuzz::{self, FuzzConfig};
+use crate::template::{examples, registry, watch, Puzzle};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Run a solution against the puzzle input, against all of its examples if `examples` is set,
+/// or against generated inputs if a [`FuzzConfig`] is passed.
+/// When running examples or fuzzing, exits with an error if an example did not produce its expected answer
+/// or the variants of a part disagree.
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    examples: bool,
+    fuzz: Option<FuzzConfig>,
+) {
+    // NOTE: dhat needs its own build profile and global allocator, so it always runs the solution binary.
+    if !dhat {
+        if let Some(solution) = registry::find_for_profile(puzzle, release) {
+            if let Some(config) = fuzz {
+                if !fuzz::run(solution, config) {
+                    process::exit(1);
+                }
+            } else if examples {
+                if !examples::run(solution) {
+                    process::exit(1);
+                }
+            } else if let Err(e) = solution.run(None) {
+                eprintln!("Failed to run {puzzle}: {e}");
+                process::exit(1);
+            }
+            return;
+        }
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -12,12 +43,25 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+        if cfg!(feature = "alloc-stats") {
+            cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
 
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    if let Some(config) = fuzz {
+        cmd_args.extend(config.to_args());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -30,5 +74,14 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if (examples || fuzz.is_some()) && !status.success() {
+        process::exit(1);
+    }
+}
+
+/// Run a solution against the puzzle input whenever its source or data files change, and its tests if `tests` is set.
+pub fn handle_watch(puzzle: Puzzle, release: bool, tests: bool) {
+    watch::run(puzzle, release, tests);
 }
@@ -1,16 +1,55 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::compare;
+use crate::template::history::{format_timestamp, Environment, History, HistoryEntry};
 use crate::template::run_multi::run_multi;
+use crate::template::stats::{format_nanos, BenchConfig, Statistic};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Puzzle, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options of `cargo time --compare`.
+pub struct CompareOptions {
+    /// Exit with a non-zero code if a part got slower by more than this percentage.
+    pub fail_on_regression: Option<f64>,
+}
+
+/// Options of `cargo time`.
+pub struct TimeOptions {
+    /// Bench all days, including days with stored timings.
+    pub run_all: bool,
+    /// Store the timings in the readme and the timing history.
+    pub store: bool,
+    pub bench: BenchConfig,
+    /// Statistic shown in the readme and used for comparisons.
+    pub statistic: Statistic,
+    pub compare: Option<CompareOptions>,
+    /// Number of days benched concurrently.
+    pub jobs: usize,
+}
+
+pub fn handle(year: Year, day: Option<Day>, options: TimeOptions) {
+    let TimeOptions {
+        run_all,
+        store,
+        bench,
+        statistic,
+        compare: compare_options,
+        jobs,
+    } = options;
+
+    if jobs > 1 {
+        eprintln!("Benching {jobs} days concurrently, timings might be less reliable.");
+    }
+
+    let stored_timings = Timings::read_from_file(year);
 
-    let days_to_run = day.map_or_else(
+    let days_to_run: HashSet<Day> = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_options.is_some() {
+                // when comparing, re-run the days that have stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +60,36 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let puzzles_to_run = days_to_run
+        .into_iter()
+        .map(|day| Puzzle::new(year, day))
+        .collect();
+
+    let timings = run_multi(&puzzles_to_run, true, Some(bench), jobs).unwrap();
+
+    let regressions = compare_options.map_or(0, |options| {
+        let deltas = compare::compare(&stored_timings, &timings, statistic);
+        compare::print(&deltas, statistic, options.fail_on_regression);
+
+        options.fail_on_regression.map_or(0, |threshold| {
+            deltas
+                .iter()
+                .filter(|delta| delta.is_regression(threshold))
+                .count()
+        })
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        let entry = HistoryEntry::now(Environment::detect(true), timings);
+        if let Err(e) = History::append(year, &entry) {
+            eprintln!("Failed to append to timing history: {e}");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, statistic) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +98,56 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!("\n{regressions} part(s) regressed beyond the allowed threshold.");
+        process::exit(1);
+    }
+}
+
+/// Print how the timings of a day evolved over all stored runs.
+pub fn history(year: Year, day: Day, statistic: Statistic) {
+    let history = History::read_from_file(year);
+
+    println!("{ANSI_BOLD}Day {day} ({statistic}){ANSI_RESET}");
+
+    let mut previous: Option<f64> = None;
+    let mut has_entries = false;
+
+    for (entry, timing) in history.for_day(day) {
+        has_entries = true;
+
+        let env = &entry.environment;
+        let revision = env
+            .git_revision
+            .as_deref()
+            .map_or("-", |x| &x[..x.len().min(8)]);
+
+        // NOTE: the change is relative to the previous run, based on the total of both parts.
+        let total = timing.total_of(statistic);
+        let change = previous.filter(|x| *x > 0.0).map_or(String::new(), |x| {
+            format!(" ({:+.1}%)", (total - x) / x * 100.0)
+        });
+        previous = Some(total);
+
+        println!(
+            "{} | {revision} | part 1: {} | part 2: {} | total: {}{change} | {} | {} | {} | {}",
+            format_timestamp(entry.timestamp),
+            timing
+                .format_part(1, statistic)
+                .unwrap_or_else(|| "-".into()),
+            timing
+                .format_part(2, statistic)
+                .unwrap_or_else(|| "-".into()),
+            format_nanos(total),
+            env.profile,
+            env.rustc_version.as_deref().unwrap_or("-"),
+            env.cpu.as_deref().unwrap_or("-"),
+            env.hostname.as_deref().unwrap_or("-"),
+        );
+    }
+
+    if !has_entries {
+        println!("No stored runs. Run `cargo time {day} --store` to record one.");
+    }
 }
@@ -0,0 +1,97 @@
+use std::{collections::HashSet, fs, path::Path, process};
+
+use crate::template::record::{PartRecord, Status};
+use crate::template::run_multi::run_puzzles;
+use crate::template::{
+    all_days, registry, Day, Puzzle, Year, ANSI_BOLD, ANSI_BOLD_GREEN, ANSI_BOLD_RED, ANSI_RESET,
+};
+
+/// Outcome of a part when verified against `data/outputs`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Fail,
+    /// There is no expected answer to compare with.
+    Missing,
+    /// The part is not solved and has no expected answer either.
+    Unsolved,
+}
+
+impl Outcome {
+    fn of(record: &PartRecord) -> Self {
+        match (record.status, &record.expected) {
+            (Status::Match, _) => Outcome::Pass,
+            (Status::Mismatch, _) => Outcome::Fail,
+            (Status::Unverified, _) => Outcome::Missing,
+            // a part that stopped producing an answer is a regression.
+            (Status::Failed, Some(_)) => Outcome::Fail,
+            (Status::Failed, None) => Outcome::Unsolved,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Outcome::Pass => format!("{ANSI_BOLD_GREEN}pass{ANSI_RESET}   "),
+            Outcome::Fail => format!("{ANSI_BOLD_RED}FAIL{ANSI_RESET}   "),
+            Outcome::Missing => "missing".into(),
+            Outcome::Unsolved => "-      ".into(),
+        }
+    }
+}
+
+pub fn handle(year: Year, days: &[Day], is_release: bool) {
+    let is_scaffolded = |puzzle: &Puzzle| {
+        Path::new(&puzzle.bin_path()).exists() || registry::find(*puzzle).is_some()
+    };
+
+    let puzzles: HashSet<Puzzle> = if days.is_empty() {
+        all_days()
+            .map(|day| Puzzle::new(year, day))
+            .filter(is_scaffolded)
+            .collect()
+    } else {
+        days.iter().map(|day| Puzzle::new(year, *day)).collect()
+    };
+
+    let results = run_puzzles(&puzzles, is_release, None, 1);
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("Day | Part 1  | Part 2");
+
+    let mut failures = 0;
+
+    for (puzzle, records) in &results {
+        let expected = fs::read_to_string(puzzle.data_path("outputs", "txt")).unwrap_or_default();
+
+        let outcome = |part: u8| match records.iter().find(|r| r.part == part) {
+            Some(record) => Outcome::of(record),
+            // the part did not report a result, e.g. because the solution panicked.
+            None if expected
+                .lines()
+                .nth(part as usize - 1)
+                .is_some_and(|x| !x.trim().is_empty()) =>
+            {
+                Outcome::Fail
+            }
+            None => Outcome::Unsolved,
+        };
+
+        let (part_1, part_2) = (outcome(1), outcome(2));
+        failures += [part_1, part_2]
+            .iter()
+            .filter(|x| **x == Outcome::Fail)
+            .count();
+
+        println!(" {} | {} | {}", puzzle.day, part_1.label(), part_2.label());
+    }
+
+    let not_scaffolded = puzzles.len() - results.len();
+    if not_scaffolded > 0 {
+        println!("{not_scaffolded} day(s) have not been scaffolded.");
+    }
+
+    if failures > 0 {
+        eprintln!("\n{failures} part(s) do not match the expected answer.");
+        process::exit(1);
+    }
+}
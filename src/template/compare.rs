@@ -0,0 +1,177 @@
+/// Module that compares fresh benchmark timings with stored timings.
+use crate::template::stats::{format_nanos, Statistic};
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_BOLD_GREEN, ANSI_BOLD_RED, ANSI_RESET};
+
+/// Change of a single part between stored and fresh timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub stored_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl PartDelta {
+    pub fn delta_nanos(&self) -> f64 {
+        self.new_nanos - self.stored_nanos
+    }
+
+    /// Relative change in percent. Positive values mean the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        if self.stored_nanos == 0.0 {
+            return 0.0;
+        }
+        self.delta_nanos() / self.stored_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Compare all parts that are present in both `stored` and `new`.
+pub fn compare(stored: &Timings, new: &Timings, statistic: Statistic) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for timing in &new.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in [1, 2] {
+            if let (Some(stored_nanos), Some(new_nanos)) = (
+                stored_timing.part_nanos(part, statistic),
+                timing.part_nanos(part, statistic),
+            ) {
+                deltas.push(PartDelta {
+                    day: timing.day,
+                    part,
+                    stored_nanos,
+                    new_nanos,
+                });
+            }
+        }
+    }
+
+    deltas.sort_unstable_by_key(|d| (d.day, d.part));
+    deltas
+}
+
+/// Print a table of deltas. Parts that exceed `threshold_percent` are highlighted as regressions.
+pub fn print(deltas: &[PartDelta], statistic: Statistic, threshold_percent: Option<f64>) {
+    println!("\n{ANSI_BOLD}Comparison with stored timings ({statistic}){ANSI_RESET}");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare with.");
+        return;
+    }
+
+    for delta in deltas {
+        let color = if threshold_percent.is_some_and(|t| delta.is_regression(t)) {
+            ANSI_BOLD_RED
+        } else if delta.delta_nanos() < 0.0 {
+            ANSI_BOLD_GREEN
+        } else {
+            ""
+        };
+
+        let sign = if delta.delta_nanos() < 0.0 { "-" } else { "+" };
+
+        println!(
+            "Day {} part {}: {} -> {} ({color}{sign}{}, {:+.1}%{ANSI_RESET})",
+            delta.day,
+            delta.part,
+            format_nanos(delta.stored_nanos),
+            format_nanos(delta.new_nanos),
+            format_nanos(delta.delta_nanos().abs()),
+            delta.change_percent(),
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, PartDelta};
+    use crate::{
+        day,
+        template::{
+            stats::Statistic,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(day: u8, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            parse: None,
+            parse_stats: None,
+            part_1: part_1.map(|x| x.into()),
+            part_2: part_2.map(|x| x.into()),
+            total_nanos: 0.0,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_variant: None,
+            part_2_variant: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+        }
+    }
+
+    #[test]
+    fn compares_common_parts() {
+        let stored = Timings {
+            data: vec![
+                timing(1, Some("10.0ms"), Some("20.0ms")),
+                timing(2, Some("1.0µs"), None),
+            ],
+        };
+        let new = Timings {
+            data: vec![
+                timing(1, Some("12.0ms"), Some("10.0ms")),
+                timing(2, Some("1.0µs"), Some("5.0µs")),
+                timing(3, Some("1.0s"), None),
+            ],
+        };
+
+        let deltas = compare(&stored, &new, Statistic::Mean);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(
+            deltas[0],
+            PartDelta {
+                day: day!(1),
+                part: 1,
+                stored_nanos: 10_000_000.0,
+                new_nanos: 12_000_000.0,
+            }
+        );
+        assert!((deltas[0].change_percent() - 20.0).abs() < 1e-9);
+        assert!((deltas[1].change_percent() + 50.0).abs() < 1e-9);
+        assert_eq!(deltas[2].change_percent(), 0.0);
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let delta = PartDelta {
+            day: day!(1),
+            part: 1,
+            stored_nanos: 100.0,
+            new_nanos: 110.0,
+        };
+        assert!(delta.is_regression(5.0));
+        assert!(!delta.is_regression(10.0));
+    }
+
+    #[test]
+    fn skips_statistics_that_are_not_stored() {
+        let stored = Timings {
+            data: vec![timing(1, Some("10.0ms"), None)],
+        };
+        let new = Timings {
+            data: vec![timing(1, Some("12.0ms"), None)],
+        };
+        assert!(compare(&stored, &new, Statistic::Median).is_empty());
+    }
+}
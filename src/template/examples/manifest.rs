@@ -0,0 +1,86 @@
+/// Example manifests list the example files of a day, the parts they apply to and their expected answers.
+///
+/// ```json
+/// [
+///     { "file": "01.txt", "part_1": "11", "part_2": "31" },
+///     { "file": "01-2.txt", "part_2": "48" }
+/// ]
+/// ```
+///
+/// An example applies to a part if it has a key for it. Answers are strings, `null` means the answer is not known yet.
+///
+/// NOTE: this module is shared with the build script, so it must not depend on the rest of the crate.
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+/// An example input of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example file in the `examples` folder.
+    pub file: String,
+    /// The parts the example applies to, as `(part number, expected answer)`.
+    pub parts: Vec<(u8, Option<String>)>,
+}
+
+impl Example {
+    pub fn applies_to(&self, part: u8) -> bool {
+        self.parts.iter().any(|(p, _)| *p == part)
+    }
+
+    /// The expected answer of a part, if the example applies to it and the answer is known.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.parts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .and_then(|(_, expected)| expected.as_deref())
+    }
+}
+
+/// Parse the contents of an example manifest.
+pub fn parse_manifest(json: &str) -> Result<Vec<Example>, String> {
+    let json = JsonValue::from_str(json).or(Err("not valid JSON file."))?;
+
+    json.get::<Vec<JsonValue>>()
+        .ok_or("expected JSON document to be an array.")?
+        .iter()
+        .map(Example::try_from)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected example.file to be a string.")?;
+
+        let mut parts = vec![];
+
+        for part in [1, 2] {
+            let key = format!("part_{part}");
+            if let Some(v) = json.get(&key) {
+                let expected = match v {
+                    JsonValue::Null => None,
+                    JsonValue::String(x) => Some(x.clone()),
+                    _ => return Err(format!("Expected example.{key} to be null or string.")),
+                };
+                parts.push((part, expected));
+            }
+        }
+
+        if parts.is_empty() {
+            return Err(format!("Expected example `{file}` to apply to a part."));
+        }
+
+        Ok(Example { file, parts })
+    }
+}
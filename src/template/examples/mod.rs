@@ -0,0 +1,234 @@
+/// Example inputs of a puzzle and the answers they are expected to produce.
+///
+/// Examples are declared in a manifest next to the example files, e.g. `data/examples/01.json` (see [`Example`]).
+/// Days without a manifest use `01.txt` for both parts, or `01-<part>.txt` where present, without expected answers.
+///
+/// The build script generates a test per example and part of a manifest, which is included by the `solution!` macro.
+/// `cargo solve <day> --examples` runs all examples of a day.
+use std::{env, fs, path::PathBuf};
+
+use crate::template::record::{PartRecord, Status};
+use crate::template::registry::Solution;
+use crate::template::runner::{parse_failed, run_parse, run_part};
+use crate::template::{Params, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+mod manifest;
+
+pub use manifest::{parse_manifest, Example};
+
+/// Path of the example manifest of a puzzle, e.g. `data/examples/01.json`.
+pub fn manifest_path(puzzle: Puzzle) -> PathBuf {
+    puzzle.data_path("examples", "json")
+}
+
+/// Read the examples of a puzzle from its manifest, falling back to the default example files.
+pub fn read_examples(puzzle: Puzzle) -> Result<Vec<Example>, String> {
+    let path = manifest_path(puzzle);
+
+    if !path.exists() {
+        return Ok(default_examples(puzzle));
+    }
+
+    fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| parse_manifest(&json))
+        .map_err(|e| format!("invalid example manifest \"{}\": {e}", path.display()))
+}
+
+/// Part-specific example files (`01-2.txt`, as read by [`read_file_part`](crate::template::read_file_part))
+/// take precedence over the shared one.
+fn default_examples(puzzle: Puzzle) -> Vec<Example> {
+    let exists = |file: &str| puzzle.data_path_with_name("examples", file).exists();
+
+    let mut examples = vec![];
+    let mut shared_parts = vec![];
+
+    for part in [1, 2] {
+        let file = format!("{}-{part}.txt", puzzle.day);
+        if exists(&file) {
+            examples.push(Example {
+                file,
+                parts: vec![(part, None)],
+            });
+        } else {
+            shared_parts.push((part, None));
+        }
+    }
+
+    let file = format!("{}.txt", puzzle.day);
+    if !shared_parts.is_empty() && exists(&file) {
+        examples.insert(
+            0,
+            Example {
+                file,
+                parts: shared_parts,
+            },
+        );
+    }
+
+    examples
+}
+
+/// Helper function that reads an example file to a string.
+#[must_use]
+pub fn read_example(puzzle: Puzzle, file: &str) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd.join(puzzle.data_path_with_name("examples", file));
+    let f = fs::read_to_string(filepath);
+    f.unwrap_or_else(|_| panic!("could not open example file `{file}`"))
+}
+
+/// Helper function that reads the [`Params`] of an example file, e.g. `data/examples/12-small.params` for `12-small.txt`.
+#[must_use]
+pub fn read_example_params(puzzle: Puzzle, file: &str) -> Params {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join(puzzle.data_path_with_name("examples", file))
+        .with_extension("params");
+    Params::read_from_file(&filepath)
+        .unwrap_or_else(|e| panic!("could not read params of example file `{file}`: {e}"))
+}
+
+/// Run the registered parts of a solution against all of its examples, printing results like the solution binary does.
+/// Returns whether all examples produced their expected answers.
+pub fn run(solution: &Solution) -> bool {
+    let puzzle = (solution.puzzle)();
+
+    let examples = match read_examples(puzzle) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("Failed to read examples: {e}");
+            return false;
+        }
+    };
+
+    if examples.is_empty() {
+        eprintln!("No examples found for {puzzle}.");
+        return false;
+    }
+
+    let mut records: Vec<PartRecord> = vec![];
+
+    for example in &examples {
+        println!("{ANSI_BOLD}Example {}{ANSI_RESET}", example.file);
+
+        let input = read_example(puzzle, &example.file);
+        let params = read_example_params(puzzle, &example.file);
+        let expected: Vec<String> = [1, 2]
+            .map(|part| example.expected(part).unwrap_or_default().to_owned())
+            .into();
+
+        let (parsed, _) = run_parse(solution, &input, &params, None);
+
+        for (part, func) in solution.parts {
+            if example.applies_to(*part) {
+                records.push(match &parsed {
+                    Ok(parsed) => {
+                        run_part(solution, *part, *func, parsed.as_ref(), &expected, None)
+                    }
+                    Err(e) => parse_failed(puzzle, *part, &expected, e),
+                });
+            }
+        }
+    }
+
+    let failed = records.iter().filter(|r| is_failure(r)).count();
+    let verified = records.iter().filter(|r| r.expected.is_some()).count();
+
+    println!("---");
+    if verified == 0 {
+        println!(
+            "No expected answers declared. Add them to \"{}\".",
+            manifest_path(puzzle).display()
+        );
+    } else {
+        println!(
+            "{} of {verified} example answers matched.",
+            verified - failed
+        );
+    }
+
+    failed == 0
+}
+
+/// A part fails an example if it does not produce the expected answer.
+/// Parts without an expected answer can not fail.
+fn is_failure(record: &PartRecord) -> bool {
+    record.expected.is_some() && record.status != Status::Match
+}
+
+/// Check a part of a solution and its variants against an example. Called by the tests generated from example manifests.
+pub fn check(solution: &Solution, file: &str, part: u8, expected: Option<&str>) {
+    let puzzle = (solution.puzzle)();
+
+    let Some((_, func)) = solution.parts.iter().find(|(p, _)| *p == part) else {
+        panic!("example `{file}` applies to part {part}, which is not part of the solution");
+    };
+
+    let parsed = solution
+        .parse(
+            &read_example(puzzle, file),
+            &read_example_params(puzzle, file),
+        )
+        .unwrap_or_else(|e| panic!("could not parse example `{file}`:\n{e}"));
+
+    let result = func(parsed.as_ref());
+
+    if let Some(expected) = expected {
+        assert_eq!(result.as_deref(), Some(expected), "example `{file}`");
+    }
+
+    for variant in solution.variants_of(part) {
+        assert_eq!(
+            (variant.func)(parsed.as_ref()),
+            result,
+            "variant `{}` disagrees with part {part} on example `{file}`",
+            variant.name
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_manifest, Example};
+
+    #[test]
+    fn parses_manifest() {
+        let examples = parse_manifest(
+            r#"[
+                { "file": "01.txt", "part_1": "11", "part_2": null },
+                { "file": "01-2.txt", "part_2": "48" }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    file: "01.txt".into(),
+                    parts: vec![(1, Some("11".into())), (2, None)],
+                },
+                Example {
+                    file: "01-2.txt".into(),
+                    parts: vec![(2, Some("48".into()))],
+                },
+            ]
+        );
+
+        assert!(examples[0].applies_to(2));
+        assert_eq!(examples[0].expected(2), None);
+        assert!(!examples[1].applies_to(1));
+        assert_eq!(examples[1].expected(2), Some("48"));
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        assert!(parse_manifest(r#"{ "file": "01.txt" }"#).is_err());
+        assert!(parse_manifest(r#"[{ "part_1": "11" }]"#).is_err());
+        assert!(parse_manifest(r#"[{ "file": "01.txt", "part_1": 11 }]"#).is_err());
+        assert!(parse_manifest(r#"[{ "file": "01.txt" }]"#).is_err());
+    }
+}
@@ -0,0 +1,438 @@
+/// Differential fuzzing of solutions with [variants](crate::template::registry::Variant).
+///
+/// A day registers a generator of random, valid inputs next to its variants:
+///
+/// ```ignore
+/// advent_of_code::solution!(13, Day13, variants { 1 => fewest_tokens_solved }, generator = generate_games);
+///
+/// fn generate_games(rng: &mut Rng) -> String { /* ... */ }
+/// ```
+///
+/// `cargo solve <day> --fuzz` runs every part and its variants on generated inputs. If they disagree,
+/// the input is shrunk to a minimal reproducer while the disagreement persists.
+/// Panics count as answers, so a variant that panics disagrees with one that does not.
+use std::{
+    any::Any,
+    fmt::{self, Display},
+    fs,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::registry::{part_name, Solution};
+use crate::template::solution::PartFn;
+use crate::template::stats::parse_count;
+use crate::template::{read_params, Params, ANSI_BOLD_RED, ANSI_ITALIC, ANSI_RESET};
+
+/// Signature of an input generator as registered by the `solution!` macro.
+pub type GenerateFn = fn(&mut Rng) -> String;
+
+/// Number of generated inputs if `--cases` is not passed.
+const DEFAULT_CASES: u128 = 1_000;
+
+/* -------------------------------------------------------------------------- */
+
+/// A small, seedable pseudo-random number generator (xorshift64*) for input generators.
+/// Not suitable for anything but generating test inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // the state must not be zero, mix the seed so that similar seeds give different sequences.
+        let state = seed
+            .wrapping_add(0x9E37_79B9_7F4A_7C15)
+            .wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        Rng {
+            state: state.max(1),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A number in the given range.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range of random numbers must not be empty");
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    /// A random element of a non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() as u64 - 1) as usize]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Controls how many inputs are generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FuzzConfig {
+    pub cases: u128,
+    /// Seed of the first input. Defaults to the current time, pass it again to reproduce a run.
+    pub seed: u64,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            cases: DEFAULT_CASES,
+            seed: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64),
+        }
+    }
+}
+
+impl FuzzConfig {
+    /// Read the configuration from solution arguments (e.g. `--cases 100 --seed 42`).
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| args.get(i + 1).ok_or(format!("missing value for {flag}.")))
+                .transpose()
+        };
+
+        let mut config = Self::default();
+
+        if let Some(cases) = value_of("--cases")? {
+            config.cases = parse_count(cases, "--cases")?;
+        }
+        if let Some(seed) = value_of("--seed")? {
+            config.seed = seed
+                .parse()
+                .map_err(|_| "expected a number for --seed.".to_string())?;
+        }
+
+        Ok(config)
+    }
+
+    /// Convert the configuration back to solution arguments, e.g. to mirror it to child invocations.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--fuzz".to_string(),
+            "--cases".to_string(),
+            self.cases.to_string(),
+            "--seed".to_string(),
+            self.seed.to_string(),
+        ]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Result of running one implementation of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Answer(Option<String>),
+    Panic(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(Some(answer)) => write!(f, "gives `{answer}`"),
+            Outcome::Answer(None) => write!(f, "gives no answer"),
+            Outcome::Panic(message) => write!(f, "panics: {message}"),
+        }
+    }
+}
+
+/// The outcomes of a part and its variants on an input, if they are not all the same.
+struct Disagreement {
+    part: u8,
+    outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl Disagreement {
+    /// Whether both are disagreements of the same part, in which the same implementations panic.
+    fn is_like(&self, other: &Disagreement) -> bool {
+        let panics = |d: &Disagreement| -> Vec<bool> {
+            d.outcomes
+                .iter()
+                .map(|(_, outcome)| matches!(outcome, Outcome::Panic(_)))
+                .collect()
+        };
+        self.part == other.part && panics(self) == panics(other)
+    }
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcomes: Vec<String> = self
+            .outcomes
+            .iter()
+            .map(|(name, outcome)| format!("{name} {outcome}"))
+            .collect();
+        write!(f, "{}", outcomes.join(", "))
+    }
+}
+
+/// Run all parts with variants of a solution on generated inputs, and shrink the first input they disagree on.
+/// Generated inputs use the parameters of the example (e.g. `data/examples/20.params`), as they are of a similar size.
+/// Returns whether all variants agreed on all inputs.
+pub fn run(solution: &Solution, config: FuzzConfig) -> bool {
+    let puzzle = (solution.puzzle)();
+
+    let Some(generate) = solution.generator else {
+        eprintln!(
+            "No input generator registered for {puzzle}. Register one with `solution!({}, variants {{ .. }}, generator = ..)`.",
+            puzzle.day
+        );
+        return false;
+    };
+
+    if solution.variants.is_empty() {
+        eprintln!("{puzzle} has no variants to compare.");
+        return false;
+    }
+
+    let params = read_params("examples", puzzle);
+    let mut rng = Rng::new(config.seed);
+
+    // panics are expected while fuzzing, don't print them for every input.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failure = None;
+    for case in 1..=config.cases {
+        let input = generate(&mut rng);
+        if let Some(disagreement) = find_disagreement(solution, &input, &params) {
+            // keep the kind of disagreement, so that shrinking does not wander off to a different bug.
+            let input = shrink(&input, |candidate| {
+                find_disagreement(solution, candidate, &params)
+                    .is_some_and(|d| d.is_like(&disagreement))
+            });
+            let disagreement = find_disagreement(solution, &input, &params)
+                .expect("shrunk input to keep its disagreement");
+            failure = Some((case, input, disagreement));
+            break;
+        }
+    }
+
+    panic::set_hook(hook);
+
+    let Some((case, input, disagreement)) = failure else {
+        println!(
+            "Fuzz: {} inputs, all variants agree. {ANSI_ITALIC}(seed {}){ANSI_RESET}",
+            config.cases, config.seed
+        );
+        return true;
+    };
+
+    println!(
+        "Fuzz: {ANSI_BOLD_RED}✖{ANSI_RESET} variants of part {} disagree on input {case} {ANSI_ITALIC}(seed {}){ANSI_RESET}",
+        disagreement.part, config.seed
+    );
+    println!("{disagreement}");
+    println!("---");
+    println!("{}", input.trim_end());
+    println!("---");
+
+    let path = puzzle.data_path_with_name("examples", &format!("{}-fuzz.txt", puzzle.day));
+    match fs::write(&path, &input) {
+        Ok(()) => println!(
+            "Wrote the input to \"{}\", add it to the example manifest to keep it as a test.",
+            path.display()
+        ),
+        Err(e) => eprintln!("Failed to write the input: {e}"),
+    }
+
+    false
+}
+
+/// Entry point of `--fuzz` in solution binaries. Exits with an error if the variants disagree.
+pub fn run_main(solution: &Solution, args: &[String]) {
+    let config = match FuzzConfig::from_args(args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid fuzz options: {e}");
+            process::exit(1);
+        }
+    };
+
+    process::exit(if run(solution, config) { 0 } else { 1 });
+}
+
+fn find_disagreement(solution: &Solution, input: &str, params: &Params) -> Option<Disagreement> {
+    // inputs that can not be parsed don't tell anything about the parts.
+    let parsed = panic::catch_unwind(|| solution.parse(input, params))
+        .ok()?
+        .ok()?;
+
+    for (part, func) in solution.parts {
+        let variants: Vec<_> = solution.variants_of(*part).collect();
+        if variants.is_empty() {
+            continue;
+        }
+
+        let mut outcomes = vec![(part_name(*part), run_part(*func, parsed.as_ref()))];
+        outcomes.extend(
+            variants
+                .iter()
+                .map(|v| (v.name, run_part(v.func, parsed.as_ref()))),
+        );
+
+        if outcomes
+            .iter()
+            .any(|(_, outcome)| *outcome != outcomes[0].1)
+        {
+            return Some(Disagreement {
+                part: *part,
+                outcomes,
+            });
+        }
+    }
+
+    None
+}
+
+fn run_part(func: PartFn, parsed: &dyn Any) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(|| func(parsed))) {
+        Ok(answer) => Outcome::Answer(answer),
+        Err(payload) => Outcome::Panic(
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string()),
+        ),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Shrink an input while `fails` holds. Removes blocks (separated by empty lines) and lines first,
+/// then makes the numbers in the input smaller.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut current = input.to_string();
+
+    loop {
+        let smaller = remove_chunks(&current, "\n\n", &fails)
+            .or_else(|| remove_chunks(&current, "\n", &fails))
+            .or_else(|| shrink_numbers(&current, &fails));
+
+        match smaller {
+            Some(smaller) => current = smaller,
+            None => return current,
+        }
+    }
+}
+
+/// Try to remove runs of units, starting with half of them.
+fn remove_chunks(input: &str, separator: &str, fails: &impl Fn(&str) -> bool) -> Option<String> {
+    let units: Vec<&str> = input.split(separator).collect();
+    let mut size = units.len() / 2;
+
+    while size > 0 {
+        for start in (0..units.len()).step_by(size) {
+            let end = (start + size).min(units.len());
+            let candidate = [&units[..start], &units[end..]].concat().join(separator);
+
+            if !candidate.trim().is_empty() && fails(&candidate) {
+                return Some(candidate);
+            }
+        }
+        size /= 2;
+    }
+
+    None
+}
+
+/// Try to replace a number of the input with a smaller one.
+fn shrink_numbers(input: &str, fails: &impl Fn(&str) -> bool) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut start = 0;
+
+    while start < bytes.len() {
+        if !bytes[start].is_ascii_digit() {
+            start += 1;
+            continue;
+        }
+
+        let end = (start..bytes.len())
+            .find(|&i| !bytes[i].is_ascii_digit())
+            .unwrap_or(bytes.len());
+
+        if let Ok(number) = input[start..end].parse::<u64>() {
+            let mut candidates = vec![0, 1, number / 2, number.saturating_sub(1)];
+            candidates.dedup();
+
+            for smaller in candidates.into_iter().filter(|&n| n < number) {
+                let candidate = format!("{}{smaller}{}", &input[..start], &input[end..]);
+                if fails(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+
+        start = end;
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{shrink, FuzzConfig, Rng};
+
+    #[test]
+    fn generates_reproducible_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..100 {
+            let n = a.range(3..=7);
+            assert_eq!(n, b.range(3..=7));
+            assert!((3..=7).contains(&n));
+        }
+
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn shrinks_inputs() {
+        // fails if a line has two numbers that add up to at least 10.
+        let fails = |input: &str| {
+            input.lines().any(|line| {
+                line.split(',')
+                    .map(|x| x.parse::<u64>().unwrap_or_default())
+                    .sum::<u64>()
+                    >= 10
+            })
+        };
+
+        let input = "1,2\n3,4\n\n18,25\n";
+        assert_eq!(shrink(input, fails), "0,10");
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = FuzzConfig {
+            cases: 100,
+            seed: 42,
+        };
+        assert_eq!(FuzzConfig::from_args(&config.to_args()), Ok(config));
+        assert!(FuzzConfig::from_args(&["--cases".into(), "0".into()]).is_err());
+    }
+}
@@ -0,0 +1,402 @@
+/// Append-only history of stored benchmark runs.
+///
+/// Every `cargo time --store` appends one entry with the environment the timings were taken in,
+/// so the performance of a day can be followed over time. Entries are stored as JSON lines.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{year_data_dir, Day, Year};
+
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+fn history_file_path(year: Year) -> PathBuf {
+    year_data_dir(year).join(HISTORY_FILE_NAME)
+}
+
+/// Environment a benchmark run was taken in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Environment {
+    pub git_revision: Option<String>,
+    pub rustc_version: Option<String>,
+    pub profile: String,
+    pub cpu: Option<String>,
+    pub hostname: Option<String>,
+}
+
+impl Environment {
+    /// Detect the environment of the current machine and checkout.
+    pub fn detect(is_release: bool) -> Self {
+        Self {
+            git_revision: git_revision(Path::new(".git")),
+            // NOTE: set by the build script, solutions are built with the same compiler.
+            rustc_version: option_env!("AOC_RUSTC_VERSION").map(|x| x.to_string()),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            cpu: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|x| cpu_model(&x)),
+            hostname: fs::read_to_string("/proc/sys/kernel/hostname")
+                .or_else(|_| fs::read_to_string("/etc/hostname"))
+                .ok()
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty()),
+        }
+    }
+}
+
+/// Resolve the revision of `HEAD` by reading the git directory, without calling git.
+fn git_revision(git_dir: &Path) -> Option<String> {
+    // worktrees and submodules use a `.git` file pointing to the actual git directory.
+    if git_dir.is_file() {
+        let content = fs::read_to_string(git_dir).ok()?;
+        let target = content.trim().strip_prefix("gitdir:")?.trim();
+        return git_revision(&git_dir.parent()?.join(target));
+    }
+
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let Some(reference) = head.trim().strip_prefix("ref:") else {
+        // detached `HEAD` contains the revision.
+        return Some(head.trim().to_string());
+    };
+    let reference = reference.trim();
+
+    // linked worktrees only keep their own `HEAD`, branches live in the git directory named by `commondir`.
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    };
+
+    [git_dir, common_dir.as_path()]
+        .iter()
+        .find_map(|dir| fs::read_to_string(dir.join(reference)).ok())
+        .map(|x| x.trim().to_string())
+        .or_else(|| {
+            let packed = fs::read_to_string(common_dir.join("packed-refs")).ok()?;
+            packed_ref(&packed, reference)
+        })
+}
+
+fn packed_ref(packed_refs: &str, reference: &str) -> Option<String> {
+    packed_refs
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == reference)
+        .map(|(revision, _)| revision.to_string())
+}
+
+fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "model name")
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the UNIX epoch.
+    pub timestamp: u64,
+    pub environment: Environment,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create an entry for timings that were taken just now.
+    pub fn now(environment: Environment, timings: Timings) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs()),
+            environment,
+            timings,
+        }
+    }
+}
+
+/// All stored benchmark runs of a year, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Append an entry to the history file.
+    pub fn append(year: Year, entry: &HistoryEntry) -> Result<(), io::Error> {
+        let json = JsonValue::from(entry)
+            .stringify()
+            .map_err(io::Error::other)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(history_file_path(year))?;
+        writeln!(file, "{json}")
+    }
+
+    /// Read the history file. If not present, returns an empty history. Malformed entries are skipped.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(history_file_path(year))
+            .map(|content| Self::from_lines(&content))
+            .unwrap_or_default()
+    }
+
+    fn from_lines(content: &str) -> Self {
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                JsonValue::from_str(line)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| HistoryEntry::try_from(&json))
+                    .map_err(|e| eprintln!("Skipping malformed history entry: {e}"))
+                    .ok()
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Timings of a day in every run that benched it.
+    pub fn for_day(&self, day: Day) -> impl Iterator<Item = (&HistoryEntry, &Timing)> {
+        self.entries.iter().filter_map(move |entry| {
+            entry
+                .timings
+                .data
+                .iter()
+                .find(|t| t.day == day)
+                .map(|timing| (entry, timing))
+        })
+    }
+}
+
+/// Format a UNIX timestamp as UTC date and time, e.g. `2024-12-01 05:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // convert days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3_600,
+        seconds % 3_600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: &Option<String>) -> JsonValue {
+    match value {
+        Some(x) => JsonValue::String(x.clone()),
+        None => JsonValue::Null,
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let env = &value.environment;
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("git_revision".into(), optional_string(&env.git_revision));
+        map.insert("rustc_version".into(), optional_string(&env.rustc_version));
+        map.insert("profile".into(), JsonValue::String(env.profile.clone()));
+        map.insert("cpu".into(), optional_string(&env.cpu));
+        map.insert("hostname".into(), optional_string(&env.hostname));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let optional_string = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected entry.{key} to be null or string."))
+                .map(|v| v.cloned())
+        };
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?;
+
+        Ok(HistoryEntry {
+            timestamp: timestamp as u64,
+            environment: Environment {
+                git_revision: optional_string("git_revision")?,
+                rustc_version: optional_string("rustc_version")?,
+                profile: profile.clone(),
+                cpu: optional_string("cpu")?,
+                hostname: optional_string("hostname")?,
+            },
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use std::{env, fs, process};
+
+    use super::{
+        cpu_model, format_timestamp, git_revision, packed_ref, Environment, History, HistoryEntry,
+    };
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_entry(timestamp: u64, part_1: &str) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            environment: Environment {
+                git_revision: Some("4b825dc642cb6eb9a060e54bf8d69288fbee4904".into()),
+                rustc_version: Some("rustc 1.83.0 (90b35a623 2024-11-26)".into()),
+                profile: "release".into(),
+                cpu: None,
+                hostname: Some("localhost".into()),
+            },
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(part_1.into()),
+                    part_2: None,
+                    total_nanos: 1e+6,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn reads_appended_entries() {
+        let lines = [get_mock_entry(1, "1.0ms"), get_mock_entry(2, "2.0ms")]
+            .iter()
+            .map(|entry| JsonValue::from(entry).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let history = History::from_lines(&format!("{lines}\nnot json\n"));
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(
+            history.entries[0].environment,
+            get_mock_entry(1, "1.0ms").environment
+        );
+
+        let day_1: Vec<_> = history
+            .for_day(day!(1))
+            .map(|(entry, timing)| (entry.timestamp, timing.part_1.clone().unwrap()))
+            .collect();
+        assert_eq!(day_1, vec![(1, "1.0ms".into()), (2, "2.0ms".into())]);
+        assert_eq!(history.for_day(day!(2)).count(), 0);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn reads_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7\nflags\t: fpu";
+        assert_eq!(cpu_model(cpuinfo), Some("Intel(R) Core(TM) i7".into()));
+        assert_eq!(cpu_model("processor\t: 0"), None);
+    }
+
+    #[test]
+    fn reads_packed_refs() {
+        let packed = "# pack-refs with: peeled fully-peeled sorted\nabc123 refs/heads/main\ndef456 refs/tags/v1\n";
+        assert_eq!(packed_ref(packed, "refs/heads/main"), Some("abc123".into()));
+        assert_eq!(packed_ref(packed, "refs/heads/other"), None);
+    }
+
+    #[test]
+    fn resolves_revisions_of_linked_worktrees() {
+        let root = env::temp_dir().join(format!("aoc-history-worktree-{}", process::id()));
+        let common_dir = root.join("repo/.git");
+        let worktree_dir = common_dir.join("worktrees/feature");
+        let checkout = root.join("feature");
+
+        fs::create_dir_all(&worktree_dir).unwrap();
+        fs::create_dir_all(&checkout).unwrap();
+        fs::write(
+            checkout.join(".git"),
+            format!("gitdir: {}\n", worktree_dir.display()),
+        )
+        .unwrap();
+        fs::write(worktree_dir.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+        fs::write(worktree_dir.join("commondir"), "../..\n").unwrap();
+        fs::write(
+            common_dir.join("packed-refs"),
+            "# pack-refs with: peeled fully-peeled sorted\n4b825dc642cb6eb9a060e54bf8d69288fbee4904 refs/heads/feature\n",
+        )
+        .unwrap();
+
+        let revision = git_revision(&checkout.join(".git"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            revision.as_deref(),
+            Some("4b825dc642cb6eb9a060e54bf8d69288fbee4904")
+        );
+    }
+}
@@ -1,15 +1,35 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod fuzz;
+pub mod params;
+pub mod parse_error;
+pub mod record;
+pub mod registry;
 pub mod runner;
+pub mod solution;
+pub mod stats;
 
 pub use day::*;
+pub use params::Params;
+pub use parse_error::{parse_all, parse_lines, ParseError};
+pub use puzzle::*;
+pub use solution::Solution;
+pub use year::*;
 
+mod compare;
 mod day;
+mod history;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -18,55 +38,176 @@ pub const ANSI_BOLD_GREEN: &str = "\x1b[1;32m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// Accepts either a [`Puzzle`] or a bare [`Day`] of the default year.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.unwrap_or_else(|_| panic!("could not open {} file", folder))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath =
+        cwd.join(puzzle.data_path_with_name(folder, &format!("{}-{part}.txt", puzzle.day)));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads the [`Params`] of an input from its sidecar file, e.g. `data/examples/18.params`.
+/// Returns empty parameters if the file does not exist.
+#[must_use]
+pub fn read_params(folder: &str, puzzle: impl Into<Puzzle>) -> Params {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd.join(puzzle.into().data_path(folder, "params"));
+    Params::read_from_file(&filepath)
+        .unwrap_or_else(|e| panic!("could not read {} params: {e}", folder))
+}
+
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// Also defines the constant `SOLUTION`, which the solution registry uses to run the parts in-process,
+/// and includes the tests generated from the example manifest of the day (see [`examples`](crate::template::examples)).
+///
+/// The puzzle year can be set with a leading `year = <year>` parameter, otherwise the default year is used.
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts either take the input (`fn(&str)`), or the input and its parameters (`fn(&str, &Params)`, see [`Params`]).
+/// Alternatively, the last parameter names a type implementing [`Solution`], e.g. `solution!(7, Day07)`.
+///
+/// Alternative implementations of a part are registered with a trailing `variants { <part> => <function>, ... }`,
+/// e.g. `solution!(13, Day13, variants { 1 => fewest_tokens_solved })`. Variants take the same arguments as the parts,
+/// or the parsed input for trait-based solutions. The runner checks that they agree with the part and benchmarks them.
+/// A trailing `generator = <function>` registers a generator of random inputs for fuzzing the variants (see [`fuzz`]).
 #[macro_export]
 macro_rules! solution {
+    (year = $year:expr, $day:expr, variants { $($variants:tt)* } $(, generator = $generator:path)?) => {
+        $crate::solution!(@impl $crate::year!($year), $day, [part_one, 1] [part_two, 2]; variants { $($variants)* } $(; generator = $generator)?);
+    };
+    (year = $year:expr, $day:expr) => {
+        $crate::solution!(@impl $crate::year!($year), $day, [part_one, 1] [part_two, 2]);
+    };
+    (year = $year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $crate::year!($year), $day, [part_one, 1]);
+    };
+    (year = $year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $crate::year!($year), $day, [part_two, 2]);
+    };
+    (year = $year:expr, $day:expr, $solution:ty, variants { $($variants:tt)* } $(, generator = $generator:path)?) => {
+        $crate::solution!(@impl $crate::year!($year), $day, type $solution; variants { $($variants)* } $(; generator = $generator)?);
+    };
+    (year = $year:expr, $day:expr, $solution:ty) => {
+        $crate::solution!(@impl $crate::year!($year), $day, type $solution);
+    };
+    ($day:expr, variants { $($variants:tt)* } $(, generator = $generator:path)?) => {
+        $crate::solution!(@default $day, [part_one, 1] [part_two, 2]; variants { $($variants)* } $(; generator = $generator)?);
+    };
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@default $day, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@default $day, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@default $day, [part_two, 2]);
+    };
+    ($day:expr, $solution:ty, variants { $($variants:tt)* } $(, generator = $generator:path)?) => {
+        $crate::solution!(@default $day, type $solution; variants { $($variants)* } $(; generator = $generator)?);
+    };
+    ($day:expr, $solution:ty) => {
+        $crate::solution!(@default $day, type $solution);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@default $day:expr, $($parts:tt)*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        $crate::solution!(@main $crate::template::Puzzle::from(DAY), $($parts)*);
+    };
+
+    (@impl $year:expr, $day:expr, $($parts:tt)*) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The current puzzle.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new($year, DAY);
+
+        $crate::solution!(@main PUZZLE, $($parts)*);
+    };
+
+    (@main $puzzle:expr, type $solution:ty $(; variants { $($part:literal => $variant:path),* $(,)? } $(; generator = $generator:path)?)?) => {
+        $crate::solution!(
+            @entry $puzzle,
+            Some($crate::template::solution::parse::<$solution>),
+            [
+                (1, $crate::template::solution::part_one::<$solution>),
+                (2, $crate::template::solution::part_two::<$solution>)
+            ],
+            [ $($(
+                $crate::template::registry::Variant {
+                    part: $part,
+                    name: stringify!($variant),
+                    func: |parsed| $crate::template::solution::call_variant::<$solution, _>(&|p| $variant(p), parsed),
+                }
+            ),*)? ],
+            $crate::solution!(@generator $($($generator)?)?)
+        );
+    };
+
+    (@main $puzzle:expr, $( [$func:expr, $part:expr] )* $(; variants { $($variant_part:literal => $variant:path),* $(,)? } $(; generator = $generator:path)?)?) => {
+        $crate::solution!(
+            @entry $puzzle,
+            None,
+            [ $( ($part, |parsed| $crate::template::solution::call_part(&$func, parsed)) ),* ],
+            [ $($(
+                $crate::template::registry::Variant {
+                    part: $variant_part,
+                    name: stringify!($variant),
+                    func: |parsed| $crate::template::solution::call_part(&$variant, parsed),
+                }
+            ),*)? ],
+            $crate::solution!(@generator $($($generator)?)?)
+        );
+    };
+
+    (@generator) => { None };
+    (@generator $generator:path) => { Some($generator as $crate::template::fuzz::GenerateFn) };
+
+    (@entry $puzzle:expr, $parse:expr, [ $($parts:tt)* ], [ $($variants:tt)* ], $generator:expr) => {
+        /// The registry entry for this solution.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: || $puzzle,
+                parse: $parse,
+                parts: &[ $($parts)* ],
+                variants: &[ $($variants)* ],
+                generator: $generator,
+            };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
+        }
+
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            let output = $crate::template::read_file("outputs", DAY);
-            let expected : Vec<String> = output.split("\n").map(|x| x.to_owned()).collect();
-            $( run_part($func, &input, DAY, $part, &expected); )*
+            let args: Vec<String> = std::env::args().collect();
+            if args.iter().any(|x| x == "--fuzz") {
+                $crate::template::fuzz::run_main(&SOLUTION, &args);
+            }
+            if args.iter().any(|x| x == "--examples") {
+                let passed = $crate::template::examples::run(&SOLUTION);
+                std::process::exit(if passed { 0 } else { 1 });
+            }
+            $crate::template::runner::run_main(&SOLUTION);
         }
     };
 }
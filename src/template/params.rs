@@ -0,0 +1,148 @@
+/// Named puzzle parameters, for values that differ between the example and the real input.
+///
+/// Parameters are read from a sidecar file next to the input, e.g. `data/examples/18.params` for `data/examples/18.txt`:
+///
+/// ```text
+/// # the example uses a smaller grid.
+/// size = 7
+/// num_rocks = 12
+/// ```
+///
+/// Solution parts that take a second `&Params` argument receive the parameters of their input.
+/// Values missing from the file fall back to the default passed to [`Params::get_or`].
+use std::{collections::HashMap, fmt::Debug, fs, path::Path, str::FromStr};
+
+/// The parameters of a puzzle input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    /// Read parameters from a sidecar file. If not present, returns empty parameters.
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Params::from_str(&contents),
+            Err(_) if !path.exists() => Ok(Params::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Set a parameter, overwriting previous values.
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.values.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Get a parameter, returns [`None`] if not set.
+    ///
+    /// # Panics
+    /// If the value can not be parsed into `T`.
+    pub fn get<T: FromStr>(&self, name: &str) -> Option<T>
+    where
+        T::Err: Debug,
+    {
+        self.values.get(name).map(|value| {
+            value.parse().unwrap_or_else(|e| {
+                panic!("parameter `{name}` has an invalid value `{value}`: {e:?}")
+            })
+        })
+    }
+
+    /// Get a parameter, falling back to `default` if not set.
+    ///
+    /// # Panics
+    /// If the value can not be parsed into `T`.
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> T
+    where
+        T::Err: Debug,
+    {
+        self.get(name).unwrap_or(default)
+    }
+}
+
+/// Parses `name = value` lines. Empty lines and lines starting with `#` are ignored.
+impl FromStr for Params {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = HashMap::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, value) = line
+                .split_once('=')
+                .map(|(name, value)| (name.trim(), value.trim()))
+                .filter(|(name, _)| !name.is_empty())
+                .ok_or(format!("expected line {} to be `name = value`.", index + 1))?;
+
+            values.insert(name.to_string(), value.to_string());
+        }
+
+        Ok(Params { values })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Marks solution parts that only take the input.
+pub struct InputOnly;
+
+/// Marks solution parts that take the input and its [`Params`].
+pub struct WithParams;
+
+/// A solution part as accepted by the `solution!` macro, either `fn(&str)` or `fn(&str, &Params)`.
+/// The marker type only exists to keep the two implementations apart.
+pub trait Part<Marker> {
+    fn call(&self, input: &str, params: &Params) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String>> Part<InputOnly> for F {
+    fn call(&self, input: &str, _: &Params) -> Option<String> {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, &Params) -> Option<String>> Part<WithParams> for F {
+    fn call(&self, input: &str, params: &Params) -> Option<String> {
+        self(input, params)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Params;
+
+    #[test]
+    fn parses_params() {
+        let params = Params::from_str("# comment\n\nsize = 7\nnum_rocks=12\n").unwrap();
+        assert_eq!(
+            params,
+            Params::default().with("size", 7).with("num_rocks", 12)
+        );
+        assert_eq!(params.get::<usize>("size"), Some(7));
+        assert_eq!(params.get_or("threshold", 100), 100);
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert!(Params::from_str("size 7").is_err());
+        assert!(Params::from_str("= 7").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "parameter `size` has an invalid value `seven`")]
+    fn panics_on_invalid_values() {
+        let params = Params::default().with("size", "seven");
+        params.get::<usize>("size");
+    }
+}
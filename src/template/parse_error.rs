@@ -0,0 +1,313 @@
+/// Errors of input parsers, with the position of the problem in the input.
+///
+/// Parse steps of [`Solution`](crate::template::Solution)s can return a [`ParseError`], which renders the offending line:
+///
+/// ```text
+/// expected a number at line 3, column 4:
+///   3 | 12 x4
+///     |    ^
+/// ```
+///
+/// [`parse_all`] runs a nom parser on the whole input and converts its errors.
+use std::fmt::{self, Display};
+
+use nom::{
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    IResult,
+};
+
+/// An input that could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the problem, starting at 1.
+    pub line: usize,
+    /// Column of the problem in characters, starting at 1.
+    pub column: usize,
+    /// The offending line, without its line ending.
+    pub text: String,
+    /// What the parser expected at the position.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error at the start of `remaining`, which has to be a slice of `input`, e.g. the remaining input of a nom parser.
+    pub fn at(input: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + remaining.len() <= input.len())
+            .unwrap_or(input.len().saturating_sub(remaining.len()));
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..]
+                .split('\n')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Convert the error of a nom parser that was run on `input`.
+    pub fn from_nom<'a, E: NomError<'a>>(input: &'a str, err: nom::Err<E>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => ParseError::at(input, &input[input.len()..], "more input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let (remaining, expected) = e.position();
+                ParseError::at(input, remaining, expected)
+            }
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.text.trim_end_matches('\r');
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(
+            f,
+            "expected {} at line {}, column {}:",
+            self.expected, self.line, self.column
+        )?;
+        writeln!(f, "  {} | {text}", self.line)?;
+        write!(f, "  {gutter} | {}^", " ".repeat(self.column - 1))?;
+
+        if text.len() != self.text.len() {
+            write!(
+                f,
+                "\nnote: the input has Windows line endings (CRLF), which most parsers do not expect."
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Run a nom parser on the whole input. Fails unless the parser consumes all of it, apart from trailing whitespace.
+/// Parsers with other error types, like [`VerboseError`], can be converted with [`ParseError::from_nom`].
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    let (remaining, output) = parser(input).map_err(|e| ParseError::from_nom(input, e))?;
+
+    if remaining.trim().is_empty() {
+        return Ok(output);
+    }
+
+    // lists stop in front of the separator, point at the line that could not be parsed instead.
+    let remaining = remaining
+        .strip_prefix('\n')
+        .or_else(|| remaining.strip_prefix("\r\n"))
+        .unwrap_or(remaining);
+
+    Err(ParseError::at(input, remaining, "the end of the input"))
+}
+
+/// Run a nom parser on every line of the input. Fails unless the parser consumes each line, apart from trailing whitespace.
+/// Errors point at the exact position within the line, which [`parse_all`] can not do for parsers of lists.
+/// Like [`Matrix::parse`](crate::Matrix::parse), lines ending in a carriage return are rejected.
+pub fn parse_lines<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<Vec<O>, ParseError> {
+    input
+        .split_terminator('\n')
+        .map(|line| {
+            if let Some(pos) = line.find('\r') {
+                return Err(ParseError::at(input, &line[pos..], "the end of the line"));
+            }
+            match parser(line) {
+                Ok((remaining, output)) if remaining.trim().is_empty() => Ok(output),
+                Ok((remaining, _)) => Err(ParseError::at(input, remaining, "the end of the line")),
+                Err(e) => Err(ParseError::from_nom(input, e)),
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Error types of nom parsers that can be converted into a [`ParseError`].
+pub trait NomError<'a> {
+    /// The remaining input where the parser failed and a description of what it expected there.
+    fn position(&self) -> (&'a str, String);
+}
+
+impl<'a> NomError<'a> for nom::error::Error<&'a str> {
+    fn position(&self) -> (&'a str, String) {
+        (self.input, describe(self.code).to_string())
+    }
+}
+
+/// Contexts added with `nom::error::context` are appended to the description, innermost first.
+impl<'a> NomError<'a> for VerboseError<&'a str> {
+    fn position(&self) -> (&'a str, String) {
+        let Some((input, _)) = self.errors.first() else {
+            return ("", "valid input".to_string());
+        };
+
+        let expected = self
+            .errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Char(c) => Some(format!("{c:?}")),
+                VerboseErrorKind::Nom(kind) => Some(describe(*kind).to_string()),
+                VerboseErrorKind::Context(_) => None,
+            })
+            .unwrap_or_else(|| "valid input".to_string());
+
+        let contexts: Vec<&str> = self
+            .errors
+            .iter()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(*context),
+                _ => None,
+            })
+            .collect();
+
+        if contexts.is_empty() {
+            (input, expected)
+        } else {
+            (input, format!("{expected} in {}", contexts.join(" in ")))
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Digit | ErrorKind::HexDigit | ErrorKind::OctDigit => "a number",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Tag => "a fixed string",
+        ErrorKind::Char => "a fixed character",
+        ErrorKind::OneOf => "one of the allowed characters",
+        ErrorKind::NoneOf => "a different character",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value",
+        ErrorKind::SeparatedList | ErrorKind::Many1 | ErrorKind::Many1Count => "at least one item",
+        _ => "valid input",
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{char, line_ending, space1, u32},
+        error::{context, VerboseError},
+        multi::separated_list1,
+        sequence::{preceded, separated_pair},
+    };
+
+    use super::{parse_all, parse_lines, ParseError};
+
+    #[test]
+    fn parses_inputs() {
+        let input = "1 2\n3 4\n";
+        let expected = vec![(1, 2), (3, 4)];
+
+        assert_eq!(
+            parse_all(
+                input,
+                separated_list1(line_ending, separated_pair(u32, space1, u32))
+            ),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse_lines(input, separated_pair(u32, space1, u32)),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn locates_errors() {
+        let input = "1 2\n3 4\n12 x4\n";
+
+        assert_eq!(
+            parse_lines(input, separated_pair(u32, space1, u32)),
+            Err(ParseError {
+                line: 3,
+                column: 4,
+                text: "12 x4".into(),
+                expected: "a number".into(),
+            })
+        );
+
+        assert_eq!(
+            parse_all(
+                input,
+                separated_list1(line_ending, separated_pair(u32, space1, u32))
+            ),
+            Err(ParseError {
+                line: 3,
+                column: 1,
+                text: "12 x4".into(),
+                expected: "the end of the input".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn renders_errors() {
+        let error = parse_all(
+            "p=3,x",
+            preceded(tag("p="), separated_pair(u32, tag(","), u32)),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "expected a number at line 1, column 5:\n  1 | p=3,x\n    |     ^"
+        );
+
+        let input = format!("{}x", "1\n".repeat(9));
+        let error = parse_lines(&input, u32).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "expected a number at line 10, column 1:\n  10 | x\n     | ^"
+        );
+    }
+
+    #[test]
+    fn describes_verbose_errors() {
+        let input = "b 2";
+        let error = context::<_, VerboseError<&str>, _, _>(
+            "a value",
+            separated_pair(tag("b"), char(':'), u32),
+        )(input)
+        .map_err(|e| ParseError::from_nom(input, e))
+        .unwrap_err();
+
+        assert_eq!(error.expected, "':' in a value");
+        assert_eq!((error.line, error.column), (1, 2));
+    }
+
+    #[test]
+    fn mentions_windows_line_endings() {
+        let error = parse_all(
+            "1,2\r\n3,4\r\n",
+            separated_list1(tag("\n"), separated_pair(u32, tag(","), u32)),
+        )
+        .unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(error.to_string().contains("Windows line endings"));
+
+        let error = parse_lines("1,2\r\n3,4\r\n", separated_pair(u32, tag(","), u32)).unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 4));
+        assert!(error.to_string().contains("Windows line endings"));
+    }
+}
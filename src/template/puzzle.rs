@@ -0,0 +1,130 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::template::{Day, Year};
+
+/// Identifies a single puzzle of advent by its [`Year`] and [`Day`].
+///
+/// # Layout
+/// Puzzles of the default year (see [`Year::default`]) use the single-year layout
+/// (`data/inputs/05.txt`, `src/bin/05.rs`) unless a `data/<year>` directory exists.
+/// Every other year uses the year-scoped layout (`data/2023/inputs/05.txt`, `src/bin/2023_05.rs`).
+///
+/// # Display
+/// This value displays as `<year>/<day>`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle, Year};
+/// let puzzle = Puzzle::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023/08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Returns whether this puzzle lives in the year-scoped layout.
+    pub fn is_year_scoped(self) -> bool {
+        uses_year_layout(self.year)
+    }
+
+    /// Name of the solution binary, e.g. `05` or `2023_05`.
+    pub fn bin_name(self) -> String {
+        if self.is_year_scoped() {
+            format!("{}_{}", self.year, self.day)
+        } else {
+            self.day.to_string()
+        }
+    }
+
+    /// Path of the solution module, e.g. `./src/bin/05.rs`.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Path of a file with the given extension in a `data` folder, e.g. `data/inputs/05.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        self.data_path_with_name(folder, &format!("{}.{extension}", self.day))
+    }
+
+    /// Path of a file with a custom name in a `data` folder of the puzzle's layout.
+    pub fn data_path_with_name(self, folder: &str, file_name: &str) -> PathBuf {
+        year_data_dir(self.year).join(folder).join(file_name)
+    }
+}
+
+#[cfg(feature = "today")]
+impl Puzzle {
+    /// Returns the current puzzle if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+}
+
+/// Puzzles created from a bare [`Day`] belong to the default year.
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self::new(Year::default(), day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns whether the given year uses the year-scoped layout.
+pub fn uses_year_layout(year: Year) -> bool {
+    year != Year::default() || Path::new("data").join(year.to_string()).is_dir()
+}
+
+/// Root of the `data` directory for a year, e.g. `data` or `data/2023`.
+pub fn year_data_dir(year: Year) -> PathBuf {
+    if uses_year_layout(year) {
+        Path::new("data").join(year.to_string())
+    } else {
+        PathBuf::from("data")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Puzzle;
+    use crate::{day, year};
+
+    #[test]
+    fn uses_year_scoped_layout_for_other_years() {
+        let puzzle = Puzzle::new(year!(2015), day!(5));
+        assert_eq!(puzzle.bin_name(), "2015_05");
+        assert_eq!(puzzle.bin_path(), "./src/bin/2015_05.rs");
+        assert_eq!(
+            puzzle.data_path("inputs", "txt"),
+            PathBuf::from("data/2015/inputs/05.txt")
+        );
+    }
+
+    #[test]
+    fn uses_single_year_layout_for_default_year() {
+        let puzzle = Puzzle::from(day!(5));
+        assert_eq!(puzzle.bin_name(), "05");
+        assert_eq!(
+            puzzle.data_path("outputs", "txt"),
+            PathBuf::from("data/outputs/05.txt")
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::alloc::format_bytes;
+use crate::template::stats::Statistic;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Puzzle, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -25,11 +27,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,26 +49,70 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    statistic: Statistic,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // NOTE: the parse column is only shown if a solution has a separate parse step.
+    let show_parse = timings.data.iter().any(|timing| timing.parse.is_some());
+
+    let mut phases = vec![];
+    if show_parse {
+        phases.push("Parse");
+    }
+    phases.extend(["Part 1", "Part 2"]);
+
+    // NOTE: the mean is the default statistic and is not called out in the table.
+    let mut columns: Vec<String> = phases
+        .iter()
+        .map(|phase| match statistic {
+            Statistic::Mean => phase.to_string(),
+            _ => format!("{phase} ({statistic})"),
+        })
+        .collect();
+
+    // NOTE: allocation columns are only shown if allocations were counted for a solution.
+    let show_alloc = timings.data.iter().any(|timing| timing.alloc().is_some());
+    if show_alloc {
+        columns.extend(["Allocations", "Allocated", "Peak heap"].map(String::from));
+    }
+
+    let header_row = columns
+        .iter()
+        .map(|column| format!(" {column} |"))
+        .collect::<String>();
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day |{header_row}"),
+        format!("| :---: |{} :---:  |", " :---: |".repeat(columns.len() - 1)),
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = Puzzle::new(year, timing.day).bin_path();
+        let format = |x: Option<String>| format!(" `{}` |", x.unwrap_or_else(|| "-".into()));
+
+        let mut row = format!("| [Day {}]({}) |", timing.day.into_inner(), path);
+        if show_parse {
+            row.push_str(&format(timing.format_parse(statistic)));
+        }
+        row.push_str(&format(timing.format_part(1, statistic)));
+        row.push_str(&format(timing.format_part(2, statistic)));
+        if show_alloc {
+            let alloc = timing.alloc();
+            row.push_str(&format(alloc.map(|a| a.allocations.to_string())));
+            row.push_str(&format(alloc.map(|a| format_bytes(a.bytes))));
+            row.push_str(&format(alloc.map(|a| format_bytes(a.peak_bytes))));
+        }
+
+        lines.push(row);
     }
 
     lines.push(String::new());
@@ -81,18 +122,24 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    statistic: Statistic,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", year, timings, total_millis, statistic);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings, statistic: Statistic) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let total_millis = timings.total_millis_of(statistic);
+    update_content(&mut readme, year, timings, total_millis, statistic)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,28 +147,59 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::alloc::AllocStats,
+        template::stats::{Statistic, Stats},
+        template::timings::Timing,
+        template::timings::Timings,
+        template::Year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
             ],
         }
@@ -131,28 +209,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::default(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::default(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::default(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::default(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            Year::default(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +273,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            Year::default(),
+            get_mock_timings(),
+            190.0,
+            Statistic::Mean,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +300,61 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_statistic() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Stats {
+            base_nanos: 12_000_000.0,
+            mean_nanos: 10_000_000.0,
+            median_nanos: 9_000_000.0,
+            min_nanos: 8_000_000.0,
+            max_nanos: 11_000_000.0,
+            stddev_nanos: 500_000.0,
+            p95_nanos: 10_500_000.0,
+            samples: 100,
+            outliers: 0,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, Year::default(), timings, 9.0, Statistic::Median).unwrap();
+        assert!(s.contains("| Day | Part 1 (median) | Part 2 (median) |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `9.0ms` | `-` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `-` | `-` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_step() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, Year::default(), timings, 195.0, Statistic::Mean).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_alloc = Some(AllocStats {
+            allocations: 3,
+            bytes: 2048,
+            peak_bytes: 1024,
+        });
+        timings.data[0].part_2_alloc = Some(AllocStats {
+            allocations: 2,
+            bytes: 1024,
+            peak_bytes: 512,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, Year::default(), timings, 190.0, Statistic::Mean).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Allocations | Allocated | Peak heap |\n| :---: | :---: | :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `5` | `3.0 KiB` | `1.0 KiB` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` | `-` |"));
+    }
 }
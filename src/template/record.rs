@@ -0,0 +1,331 @@
+/// Machine-readable result protocol between the solution runner and the orchestrator.
+///
+/// When a solution binary is called with `--records`, the runner prints one JSON record per part in addition
+/// to its human-readable output. Records are prefixed with an ASCII record separator (see RFC 7464),
+/// so they can never be confused with an answer.
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::alloc::AllocStats;
+use crate::template::stats::Stats;
+use crate::template::{Day, Puzzle, Year};
+
+/// Bump this when the shape of a record changes.
+pub const RECORD_VERSION: u8 = 5;
+
+/// Prefix of every record line.
+pub const RECORD_SEPARATOR: char = '\x1e';
+
+/// Outcome of comparing an answer with the expected answer in `data/outputs`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the expected answer.
+    Match,
+    /// The answer differs from the expected answer.
+    Mismatch,
+    /// There is no expected answer to compare with.
+    Unverified,
+    /// The part did not produce an answer.
+    Failed,
+}
+
+impl Status {
+    pub fn evaluate(answer: Option<&str>, expected: Option<&str>) -> Self {
+        match (answer, expected) {
+            (None, _) => Status::Failed,
+            (Some(_), None) => Status::Unverified,
+            (Some(answer), Some(expected)) if answer == expected => Status::Match,
+            (Some(_), Some(_)) => Status::Mismatch,
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Match => "match",
+            Status::Mismatch => "mismatch",
+            Status::Unverified => "unverified",
+            Status::Failed => "failed",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "match" => Ok(Status::Match),
+            "mismatch" => Ok(Status::Mismatch),
+            "unverified" => Ok(Status::Unverified),
+            "failed" => Ok(Status::Failed),
+            _ => Err(format!("unknown status `{s}`.")),
+        }
+    }
+}
+
+/// Result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub puzzle: Puzzle,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub status: Status,
+    /// Duration of the part. When benched, this is the mean of all samples without outliers.
+    pub duration_nanos: u128,
+    pub samples: u128,
+    /// Duration of the first (cold) run.
+    pub base_nanos: u128,
+    /// Benchmark statistics, if the part was benched.
+    pub stats: Option<Stats>,
+    /// Duration of the parse step the part ran on, [`None`] if the solution has none.
+    /// Like `duration_nanos`, this is the mean if the parse step was benched.
+    pub parse_nanos: Option<u128>,
+    pub parse_stats: Option<Stats>,
+    /// Name of the fastest implementation of a part with variants, whose duration is recorded.
+    pub variant: Option<String>,
+    /// Allocations of the first (cold) run, if the runner counts allocations (see [`alloc`](crate::template::alloc)).
+    pub alloc: Option<AllocStats>,
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    /// Serializes the record to a single line, including the record separator.
+    pub fn to_line(&self) -> String {
+        let json = JsonValue::from(self);
+        format!("{RECORD_SEPARATOR}{}", json.stringify().unwrap())
+    }
+
+    /// Parses a line of solution output.
+    /// Returns [`None`] if the line is not a record, e.g. human-readable output.
+    pub fn from_line(line: &str) -> Option<Result<Self, String>> {
+        let json = line.strip_prefix(RECORD_SEPARATOR)?;
+        Some(
+            JsonValue::from_str(json)
+                .map_err(|_| "record is not valid JSON.".to_string())
+                .and_then(|value| PartRecord::try_from(&value)),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: &Option<String>) -> JsonValue {
+    match value {
+        Some(x) => JsonValue::String(x.clone()),
+        None => JsonValue::Null,
+    }
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("version".into(), JsonValue::Number(RECORD_VERSION.into()));
+        map.insert(
+            "year".into(),
+            JsonValue::Number(value.puzzle.year.into_inner().into()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(value.puzzle.day.into_inner().into()),
+        );
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), optional_string(&value.answer));
+        map.insert("expected".into(), optional_string(&value.expected));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "base_nanos".into(),
+            JsonValue::Number(value.base_nanos as f64),
+        );
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "parse_nanos".into(),
+            value
+                .parse_nanos
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert("variant".into(), optional_string(&value.variant));
+        map.insert(
+            "alloc".into(),
+            value
+                .alloc
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert("error".into(), optional_string(&value.error));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let optional_string = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected record.{key} to be null or string."))
+                .map(|v| v.cloned())
+        };
+
+        let version = number("version")?;
+        if version != f64::from(RECORD_VERSION) {
+            return Err(format!(
+                "Unsupported record version {version}, expected {RECORD_VERSION}."
+            ));
+        }
+
+        let year = Year::new(number("year")? as u16).ok_or("Expected record.year to be a Year.")?;
+        let day = Day::new(number("day")? as u8).ok_or("Expected record.day to be a Day.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let optional_stats = |key: &str| match json.get(key) {
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => Stats::try_from(v).map(Some),
+            None => Err(format!("Expected record.{key} to be null or an object.")),
+        };
+
+        let parse_nanos = match json.get("parse_nanos") {
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                *v.get::<f64>()
+                    .ok_or("Expected record.parse_nanos to be null or a number.")?
+                    as u128,
+            ),
+            None => return Err("Expected record.parse_nanos to be null or a number.".into()),
+        };
+
+        Ok(PartRecord {
+            puzzle: Puzzle::new(year, day),
+            part: number("part")? as u8,
+            answer: optional_string("answer")?,
+            expected: optional_string("expected")?,
+            status,
+            duration_nanos: number("duration_nanos")? as u128,
+            samples: number("samples")? as u128,
+            base_nanos: number("base_nanos")? as u128,
+            stats: optional_stats("stats")?,
+            parse_nanos,
+            parse_stats: optional_stats("parse_stats")?,
+            variant: optional_string("variant")?,
+            alloc: match json.get("alloc") {
+                Some(v) if v.is_null() => None,
+                Some(v) => Some(AllocStats::try_from(v)?),
+                None => return Err("Expected record.alloc to be null or an object.".into()),
+            },
+            error: optional_string("error")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartRecord, Status};
+    use crate::{
+        day,
+        template::{alloc::AllocStats, stats::Stats, Puzzle},
+        year,
+    };
+
+    fn get_mock_record() -> PartRecord {
+        PartRecord {
+            puzzle: Puzzle::new(year!(2024), day!(1)),
+            part: 1,
+            answer: Some("Part 1: @ @ ( ) ms (2s @ 5 samples)\nsecond line".into()),
+            expected: None,
+            status: Status::Unverified,
+            duration_nanos: 74_130,
+            samples: 100_000,
+            base_nanos: 100_000,
+            stats: Some(Stats {
+                base_nanos: 100_000.0,
+                mean_nanos: 74_130.0,
+                median_nanos: 74_000.0,
+                min_nanos: 70_000.0,
+                max_nanos: 90_000.0,
+                stddev_nanos: 1_500.5,
+                p95_nanos: 80_000.0,
+                samples: 99_990,
+                outliers: 10,
+            }),
+            parse_nanos: Some(1_200),
+            parse_stats: None,
+            variant: Some("part_one_fast".into()),
+            alloc: Some(AllocStats {
+                allocations: 12,
+                bytes: 4_096,
+                peak_bytes: 2_048,
+            }),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = get_mock_record();
+        let line = record.to_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_line(&line).unwrap().unwrap(), record);
+    }
+
+    #[test]
+    fn ignores_human_readable_output() {
+        assert!(PartRecord::from_line("Part 1: 0 (74.13ns @ 100000 samples)").is_none());
+        assert!(PartRecord::from_line("").is_none());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let line = get_mock_record()
+            .to_line()
+            .replace("\"version\":5", "\"version\":4");
+        assert!(PartRecord::from_line(&line).unwrap().is_err());
+    }
+
+    #[test]
+    fn evaluates_status() {
+        assert_eq!(Status::evaluate(Some("1"), Some("1")), Status::Match);
+        assert_eq!(Status::evaluate(Some("1"), Some("2")), Status::Mismatch);
+        assert_eq!(Status::evaluate(Some("1"), None), Status::Unverified);
+        assert_eq!(Status::evaluate(None, Some("1")), Status::Failed);
+    }
+}
@@ -0,0 +1,130 @@
+/// In-process registry of solutions.
+///
+/// Every `solution!` invocation defines a `SOLUTION` constant. With the `registry` feature,
+/// the build script compiles all `src/bin` modules into the main binary and registers these constants,
+/// so commands can call solutions directly instead of spawning `cargo run` for every day.
+use std::any::Any;
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::template::fuzz::GenerateFn;
+use crate::template::record::PartRecord;
+use crate::template::runner::run_solution;
+use crate::template::solution::{ParseFn, PartFn, RawInput};
+use crate::template::stats::BenchConfig;
+use crate::template::{read_params, Params, Puzzle};
+
+/// A solution that can be run in-process.
+pub struct Solution {
+    /// Resolves the puzzle at runtime, as the default year is read from the environment.
+    pub puzzle: fn() -> Puzzle,
+    /// The parse step of trait-based solutions. [`None`] if the parts take the input (see [`RawInput`]).
+    pub parse: Option<ParseFn>,
+    /// The registered parts, as `(part number, function)`.
+    pub parts: &'static [(u8, PartFn)],
+    /// Alternative implementations of the parts, which are checked against and benchmarked with the registered parts.
+    pub variants: &'static [Variant],
+    /// Generates random inputs to compare the variants on, see [`fuzz`](crate::template::fuzz).
+    pub generator: Option<GenerateFn>,
+}
+
+/// An alternative implementation of a part, e.g. a brute-force version of an optimised solution.
+pub struct Variant {
+    pub part: u8,
+    /// Name of the implementing function.
+    pub name: &'static str,
+    pub func: PartFn,
+}
+
+/// Name of the function implementing a part, as shown next to the names of its variants.
+pub fn part_name(part: u8) -> &'static str {
+    if part == 1 {
+        "part_one"
+    } else {
+        "part_two"
+    }
+}
+
+static REGISTRY: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Register the solutions compiled into the current binary. Only the first call has an effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = REGISTRY.set(solutions);
+}
+
+/// Look up the registered solution for a puzzle.
+pub fn find(puzzle: Puzzle) -> Option<&'static Solution> {
+    REGISTRY.get()?.iter().find(|s| (s.puzzle)() == puzzle)
+}
+
+/// Look up the registered solution for a puzzle if the current binary was built with the requested profile.
+/// Returns [`None`] otherwise, in which case callers fall back to spawning the solution binary.
+pub fn find_for_profile(puzzle: Puzzle, is_release: bool) -> Option<&'static Solution> {
+    if is_release == cfg!(debug_assertions) {
+        return None;
+    }
+    find(puzzle)
+}
+
+impl Solution {
+    /// Parse an input into the value passed to the parts.
+    pub fn parse(&self, input: &str, params: &Params) -> Result<Box<dyn Any>, String> {
+        match self.parse {
+            Some(parse) => parse(input, params),
+            None => Ok(Box::new((input.to_owned(), params.clone()) as RawInput)),
+        }
+    }
+
+    /// The variants of a part.
+    pub fn variants_of(&self, part: u8) -> impl Iterator<Item = &Variant> {
+        self.variants.iter().filter(move |v| v.part == part)
+    }
+
+    /// Run all registered parts against the puzzle input, printing results like the solution binary does.
+    /// Parts are benched if a [`BenchConfig`] is passed.
+    /// Fails without running the parts if the input or output file of the puzzle can not be read.
+    pub fn run(&self, bench: Option<BenchConfig>) -> Result<Vec<PartRecord>, String> {
+        let puzzle = (self.puzzle)();
+        let input = read_data_file("inputs", puzzle)?;
+        let params = read_params("inputs", puzzle);
+        let output = read_data_file("outputs", puzzle)?;
+        let expected: Vec<String> = output.split('\n').map(|x| x.to_owned()).collect();
+
+        Ok(run_solution(
+            self,
+            &input,
+            &params,
+            &expected,
+            bench,
+            |_| {},
+        ))
+    }
+}
+
+/// Like [`read_file`](crate::template::read_file), returning an error instead of panicking if the file is missing.
+fn read_data_file(folder: &str, puzzle: Puzzle) -> Result<String, String> {
+    let path = puzzle.data_path(folder, "txt");
+    fs::read_to_string(&path).map_err(|e| format!("could not open \"{}\": {e}", path.display()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Solution;
+    use crate::{day, template::Puzzle, year};
+
+    #[test]
+    fn fails_to_run_without_input() {
+        let solution = Solution {
+            puzzle: || Puzzle::new(year!(2024), day!(25)),
+            parse: None,
+            parts: &[],
+            variants: &[],
+            generator: None,
+        };
+
+        let error = solution.run(None).unwrap_err();
+        assert!(error.contains("inputs/25.txt"), "{error}");
+    }
+}
@@ -1,40 +1,31 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use crate::template::record::PartRecord;
+use crate::template::stats::BenchConfig;
+use crate::template::{registry, Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+use super::timings::{Timing, Timings};
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+pub fn run_multi(
+    puzzles_to_run: &HashSet<Puzzle>,
+    is_release: bool,
+    bench: Option<BenchConfig>,
+    jobs: usize,
+) -> Option<Timings> {
+    let timings: Vec<Timing> = run_puzzles(puzzles_to_run, is_release, bench, jobs)
+        .into_iter()
+        .map(|(puzzle, records)| Timing::from_records(puzzle.day, &records))
+        .collect();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
-
-    if is_timed {
+    if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,6 +37,119 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run a set of puzzles and print their output in order.
+/// With more than one job, puzzles run concurrently in their solution binaries.
+/// Returns the records of every puzzle that has been scaffolded.
+pub fn run_puzzles(
+    puzzles_to_run: &HashSet<Puzzle>,
+    is_release: bool,
+    bench: Option<BenchConfig>,
+    jobs: usize,
+) -> Vec<(Puzzle, Vec<PartRecord>)> {
+    // NOTE: use non-duplicate, sorted puzzle values.
+    let mut puzzles: Vec<Puzzle> = puzzles_to_run.iter().copied().collect();
+    puzzles.sort_unstable();
+
+    if jobs > 1 && puzzles.len() > 1 {
+        return run_parallel(&puzzles, is_release, bench, jobs);
+    }
+
+    let mut results = Vec::with_capacity(puzzles.len());
+
+    puzzles.into_iter().enumerate().for_each(|(i, puzzle)| {
+        print_header(puzzle, i > 0);
+
+        // prefer solutions compiled into this binary, fall back to spawning the solution binary.
+        let records = match registry::find_for_profile(puzzle, is_release) {
+            Some(solution) => solution
+                .run(bench)
+                .inspect_err(|e| eprintln!("Failed to run {puzzle}: {e}"))
+                .ok(),
+            None => child_commands::run_solution(puzzle, bench, is_release, None).unwrap(),
+        };
+
+        match records {
+            Some(records) => results.push((puzzle, records)),
+            None => println!("Not solved."),
+        }
+    });
+
+    results
+}
+
+/// Run puzzles on `jobs` threads. Every thread spawns solution binaries, whose output is buffered
+/// and printed as soon as all preceding puzzles have been printed.
+fn run_parallel(
+    puzzles: &[Puzzle],
+    is_release: bool,
+    bench: Option<BenchConfig>,
+    jobs: usize,
+) -> Vec<(Puzzle, Vec<PartRecord>)> {
+    // build the binaries upfront, so concurrent `cargo run` invocations do not wait on each other.
+    child_commands::build_solutions(puzzles, is_release).unwrap();
+
+    let mut results = Vec::with_capacity(puzzles.len());
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            let tx = tx.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(puzzle) = puzzles.get(i) else {
+                    break;
+                };
+
+                let mut output = child_commands::CapturedOutput::default();
+                let records =
+                    child_commands::run_solution(*puzzle, bench, is_release, Some(&mut output))
+                        .unwrap();
+
+                if tx.send((i, records, output)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (i, records, output) in rx {
+            pending.insert(i, (records, output));
+
+            while let Some((records, output)) = pending.remove(&next_to_print) {
+                let puzzle = puzzles[next_to_print];
+                print_header(puzzle, next_to_print > 0);
+                output.print();
+
+                match records {
+                    Some(records) => results.push((puzzle, records)),
+                    None => println!("Not solved."),
+                }
+
+                next_to_print += 1;
+            }
+        }
+    });
+
+    results
+}
+
+fn print_header(puzzle: Puzzle, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    let day = puzzle.day;
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,16 +163,11 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{record::PartRecord, stats::BenchConfig, Puzzle};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,28 +175,91 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Output of a solution binary, buffered when solutions run concurrently.
+    #[derive(Default)]
+    pub struct CapturedOutput {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+    }
+
+    impl CapturedOutput {
+        pub fn print(&self) {
+            self.stdout.iter().for_each(|line| println!("{line}"));
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
         }
+    }
+
+    /// Build the solution binaries of the given puzzles that have been scaffolded.
+    /// Only these binaries are built, rebuilding all of them would also replace the running binary, which is
+    /// built with a different set of features.
+    pub fn build_solutions(puzzles: &[Puzzle], is_release: bool) -> Result<(), Error> {
+        let bins: Vec<String> = puzzles
+            .iter()
+            .filter(|puzzle| Path::new(&puzzle.bin_path()).exists())
+            .map(|puzzle| puzzle.bin_name())
+            .collect();
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if bins.is_empty() {
+            return Ok(());
+        }
 
+        let mut args = vec!["build", "--quiet"];
+        for bin in &bins {
+            args.extend(["--bin", bin]);
+        }
         if is_release {
             args.push("--release");
         }
+        if cfg!(feature = "alloc-stats") {
+            args.extend(["--features", "alloc-stats"]);
+        }
+        Command::new("cargo").args(&args).status()?;
+        Ok(())
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    /// Run the solution bin for a given day.
+    /// Output is forwarded to stdout/stderr, unless `capture` is passed.
+    /// Returns [`None`] if the day has not been scaffolded yet, the records of its parts otherwise.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        bench: Option<BenchConfig>,
+        is_release: bool,
+        mut capture: Option<&mut CapturedOutput>,
+    ) -> Result<Option<Vec<PartRecord>>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(None);
+        }
+
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            puzzle.bin_name(),
+        ];
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        // count allocations in the solution binaries if this binary counts them.
+        if cfg!(feature = "alloc-stats") {
+            args.push("--features".into());
+            args.push("alloc-stats".into());
+        }
+
+        // request machine-readable records in addition to the human-readable output.
+        args.push("--".into());
+        args.push("--records".into());
+
+        if let Some(bench) = bench {
+            // mirror `--time` flag and benchmark configuration to child invocations.
+            args.push("--time".into());
+            args.extend(bench.to_args());
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while collecting records.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,148 +270,180 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
+        let capture_stderr = capture.is_some();
         let thread = thread::spawn(move || {
+            let mut lines = vec![];
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                if capture_stderr {
+                    lines.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
             });
+            lines
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let records = collect_records(
+            stdout.lines().map(|line| line.unwrap()),
+            |line| match capture.as_deref_mut() {
+                Some(output) => output.stdout.push(line.to_string()),
+                None => println!("{line}"),
+            },
+        );
 
-        thread.join().unwrap();
+        let stderr_lines = thread.join().unwrap();
+        if let Some(output) = capture {
+            output.stderr = stderr_lines;
+        }
         cmd.wait()?;
 
-        Ok(output)
+        Ok(Some(records))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    /// Split solution output into records and human-readable lines, which are passed to `forward`.
+    pub fn collect_records(
+        lines: impl Iterator<Item = String>,
+        mut forward: impl FnMut(&str),
+    ) -> Vec<PartRecord> {
+        let mut records = vec![];
+
+        for line in lines {
+            match PartRecord::from_line(&line) {
+                Some(Ok(record)) => records.push(record),
+                Some(Err(e)) => eprintln!("Could not read result record: {e}"),
+                None => forward(&line),
+            }
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        records
+    }
 
-                timings.total_nanos += nanos;
-            });
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::collect_records;
 
-        timings
-    }
+        use crate::{
+            day,
+            template::{
+                alloc::AllocStats,
+                record::{PartRecord, Status},
+                timings::Timing,
+                Puzzle,
+            },
+            year,
+        };
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+        fn record(part: u8, answer: Option<&str>, duration_nanos: u128) -> PartRecord {
+            PartRecord {
+                puzzle: Puzzle::new(year!(2024), day!(1)),
+                part,
+                answer: answer.map(|x| x.to_string()),
+                expected: None,
+                status: Status::evaluate(answer, None),
+                duration_nanos,
+                samples: 100,
+                base_nanos: duration_nanos,
+                stats: None,
+                parse_nanos: None,
+                parse_stats: None,
+                variant: None,
+                alloc: None,
+                error: None,
+            }
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+        fn collect(lines: &[String]) -> (Vec<PartRecord>, Vec<String>) {
+            let mut forwarded = vec![];
+            let records = collect_records(lines.iter().cloned(), |l| forwarded.push(l.to_string()));
+            (records, forwarded)
+        }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
+        #[test]
+        fn collects_execution_times() {
+            let (records, forwarded) = collect(&[
+                "Part 1: 0 (74.1ns @ 100000 samples)".into(),
+                record(1, Some("0"), 74).to_line(),
+                "Part 2: 10 (74.1ms @ 99999 samples)".into(),
+                record(2, Some("10"), 74_130_000).to_line(),
+                "".into(),
+            ]);
+            assert_eq!(records.len(), 2);
+            assert_eq!(forwarded.len(), 3);
+
+            let res = Timing::from_records(day!(1), &records);
+            assert_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+        }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+        #[test]
+        fn collects_with_patterns_in_answer() {
+            let (records, _) = collect(&[
+                record(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000).to_line(),
+                record(2, Some("10s"), 100_000_000).to_line(),
+            ]);
+            let res = Timing::from_records(day!(1), &records);
+            assert_eq!(res.total_nanos, 2100000000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
 
-        use crate::day;
+        #[test]
+        fn collects_parse_times() {
+            let parsed = |part, duration_nanos| PartRecord {
+                parse_nanos: Some(1_000),
+                ..record(part, Some("1"), duration_nanos)
+            };
+            let (records, _) = collect(&[parsed(1, 2_000).to_line(), parsed(2, 3_000).to_line()]);
+            let res = Timing::from_records(day!(1), &records);
+            assert_eq!(res.total_nanos, 6_000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "2.0µs");
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        fn collects_fastest_variants() {
+            let fastest = PartRecord {
+                variant: Some("part_one_fast".into()),
+                ..record(1, Some("1"), 1_000)
+            };
+            let (records, _) = collect(&[fastest.to_line(), record(2, Some("2"), 3_000).to_line()]);
+            let res = Timing::from_records(day!(1), &records);
+            assert_eq!(res.part_1.unwrap(), "1.0µs");
+            assert_eq!(res.part_1_variant.unwrap(), "part_one_fast");
+            assert_eq!(res.part_2_variant, None);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn collects_allocations() {
+            let alloc = AllocStats {
+                allocations: 3,
+                bytes: 96,
+                peak_bytes: 64,
+            };
+            let counted = PartRecord {
+                alloc: Some(alloc),
+                ..record(2, Some("2"), 3_000)
+            };
+            let (records, _) = collect(&[record(1, Some("1"), 1_000).to_line(), counted.to_line()]);
+            let res = Timing::from_records(day!(1), &records);
+            assert_eq!(res.part_1_alloc, None);
+            assert_eq!(res.part_2_alloc, Some(alloc));
+            assert_eq!(res.alloc(), Some(alloc));
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+        fn collects_missing_parts() {
+            let (records, _) = collect(&[
+                "Part 1: ✖        ".into(),
+                record(1, None, 10).to_line(),
+                "Part 2: ✖        ".into(),
+                record(2, None, 10).to_line(),
+                "".into(),
+            ]);
+            let res = Timing::from_records(day!(1), &records);
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
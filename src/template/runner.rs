@@ -1,78 +1,372 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
-use crate::template::{aoc_cli, Day, ANSI_BOLD_GREEN, ANSI_BOLD_RED, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::aoc_client::Submission;
+use crate::template::record::{PartRecord, Status};
+use crate::template::registry::{part_name, Solution, Variant};
+use crate::template::solution::PartFn;
+use crate::template::stats::{BenchConfig, Statistic, Stats};
+use crate::template::submissions::{self, SubmissionError};
+use crate::template::{
+    read_file, read_params, Params, Puzzle, ANSI_BOLD_GREEN, ANSI_BOLD_RED, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy>(
-    func: impl Fn(I) -> Option<String>,
-    input: I,
-    day: Day,
-    part: u8,
+/// Entry point of solution binaries: run a solution against the puzzle input.
+/// Reads the options passed by `solve` and the orchestrator from the command line.
+pub fn run_main(solution: &Solution) {
+    let args: Vec<String> = env::args().collect();
+
+    let bench = if args.iter().any(|x| x == "--time") {
+        match BenchConfig::from_args(&args) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                eprintln!("Invalid benchmark options: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // the orchestrator passes `--records` to read results without parsing human-readable output.
+    let print_records = args.iter().any(|x| x == "--records");
+
+    let puzzle = (solution.puzzle)();
+    let input = read_file("inputs", puzzle);
+    let params = read_params("inputs", puzzle);
+    let output = read_file("outputs", puzzle);
+    let expected: Vec<String> = output.split('\n').map(|x| x.to_owned()).collect();
+
+    run_solution(solution, &input, &params, &expected, bench, |record| {
+        if print_records {
+            println!("{}", record.to_line());
+        }
+    });
+}
+
+/// Parse an input once and run all parts of a solution on it, printing results.
+/// The parse step and the parts are benched if a [`BenchConfig`] is passed.
+/// `on_record` is called as soon as a part has finished.
+pub fn run_solution(
+    solution: &Solution,
+    input: &str,
+    params: &Params,
     expected: &[String],
-) {
-    let part_str = format!("Part {part}");
+    bench: Option<BenchConfig>,
+    mut on_record: impl FnMut(&PartRecord),
+) -> Vec<PartRecord> {
+    let puzzle = (solution.puzzle)();
+    let (parsed, parse_timing) = run_parse(solution, input, params, bench);
 
+    let records: Vec<PartRecord> = solution
+        .parts
+        .iter()
+        .map(|(part, func)| {
+            let mut record = match &parsed {
+                Ok(parsed) => run_part(solution, *part, *func, parsed.as_ref(), expected, bench),
+                Err(e) => parse_failed(puzzle, *part, expected, e),
+            };
+            if let Some(timing) = &parse_timing {
+                record.parse_nanos = Some(timing.nanos);
+                record.parse_stats = timing.stats;
+            }
+            on_record(&record);
+            record
+        })
+        .collect();
+
+    if let (Ok(_), Some(timing)) = (&parsed, &parse_timing) {
+        let total_nanos = timing.nanos + records.iter().map(|r| r.duration_nanos).sum::<u128>();
+        println!("Total: ({:.1?})", Duration::from_nanos(total_nanos as u64));
+    }
+
+    records
+}
+
+/// Duration of a parse step. Like parts, the duration is the mean if the parse step was benched.
+pub struct ParseTiming {
+    pub nanos: u128,
+    pub stats: Option<Stats>,
+}
+
+/// Run the parse step of a solution, printing its duration or error. The parse step is benched if a [`BenchConfig`] is passed.
+/// Solutions without a parse step pass the input on silently and are not timed.
+pub fn run_parse(
+    solution: &Solution,
+    input: &str,
+    params: &Params,
+    bench_config: Option<BenchConfig>,
+) -> (Result<Box<dyn Any>, String>, Option<ParseTiming>) {
+    if solution.parse.is_none() {
+        return (solution.parse(input, params), None);
+    }
+
+    let timer = Instant::now();
+    let parsed = solution.parse(input, params);
+    let base_time = timer.elapsed();
+
+    if let Err(e) = &parsed {
+        println!("Parse: {ANSI_BOLD_RED}✖{ANSI_RESET}");
+        println!("{e}");
+        return (parsed, None);
+    }
+
+    print!("Parse: ✔");
+    let stats = bench_config.map(|config| {
+        bench(
+            |input| solution.parse(input, params),
+            input,
+            &base_time,
+            &config,
+        )
+    });
+
+    print!("\r");
+    println!("Parse: ✔{}", format_duration(&base_time, stats.as_ref()));
+
+    let timing = ParseTiming {
+        nanos: stats.map_or(base_time.as_nanos(), |s| s.mean_nanos.round() as u128),
+        stats,
+    };
+
+    (parsed, Some(timing))
+}
+
+/// The record of a part that could not run because the input could not be parsed.
+pub fn parse_failed(puzzle: Puzzle, part: u8, expected: &[String], error: &str) -> PartRecord {
+    println!("Part {part}: ✖ parse error");
+
+    PartRecord {
+        puzzle,
+        part,
+        answer: None,
+        expected: expected
+            .get(part as usize - 1)
+            .filter(|x| !x.is_empty())
+            .cloned(),
+        status: Status::Failed,
+        error: Some(format!("parse error: {error}")),
+        duration_nanos: 0,
+        samples: 0,
+        base_nanos: 0,
+        stats: None,
+        parse_nanos: None,
+        parse_stats: None,
+        variant: None,
+        alloc: None,
+    }
+}
+
+/// Run a solution part and its variants, print their results and return a [`PartRecord`] describing the part.
+/// The part is benched if a [`BenchConfig`] is passed.
+///
+/// Variants have to produce the same answer as the part. If a part has variants, the record holds the duration
+/// of the fastest implementation. The answer is only submitted if all implementations agree.
+pub fn run_part(
+    solution: &Solution,
+    part: u8,
+    func: PartFn,
+    parsed: &dyn Any,
+    expected: &[String],
+    bench: Option<BenchConfig>,
+) -> PartRecord {
+    let puzzle = (solution.puzzle)();
     let expected = expected.get(part as usize - 1).cloned().unwrap_or_default();
 
-    let (result, duration, samples) = run_timed(func, input, |result| {
-        print_result(result, &part_str, "", &expected)
+    let (result, measurement) =
+        run_implementation(func, parsed, &format!("Part {part}"), &expected, bench);
+
+    let expected_answer = Some(expected.clone()).filter(|x| !x.is_empty());
+
+    let mut record = PartRecord {
+        puzzle,
+        part,
+        status: Status::evaluate(result.as_deref(), expected_answer.as_deref()),
+        error: result
+            .is_none()
+            .then(|| "solution did not return an answer".to_string()),
+        answer: result,
+        expected: expected_answer,
+        duration_nanos: 0,
+        samples: 0,
+        base_nanos: 0,
+        stats: None,
+        parse_nanos: None,
+        parse_stats: None,
+        variant: None,
+        alloc: None,
+    };
+    set_measurement(&mut record, &measurement);
+
+    let variants: Vec<&Variant> = solution.variants_of(part).collect();
+
+    if !variants.is_empty() {
+        let name = part_name(part);
+        let mut fastest = (name, measurement);
+        let mut answers = vec![format!("{name} gives {}", format_answer(&record.answer))];
+        let mut agree = true;
+
+        for variant in variants {
+            let label = format!("Part {part} › {}", variant.name);
+            let (result, measurement) =
+                run_implementation(variant.func, parsed, &label, &expected, bench);
+
+            agree &= result == record.answer;
+            answers.push(format!("{} gives {}", variant.name, format_answer(&result)));
+
+            if measurement.mean_nanos() < fastest.1.mean_nanos() {
+                fastest = (variant.name, measurement);
+            }
+        }
+
+        if agree {
+            println!(
+                "{ANSI_ITALIC}Part {part}: all variants agree, {} is the fastest.{ANSI_RESET}",
+                fastest.0
+            );
+        } else {
+            let error = format!("variants disagree: {}", answers.join(", "));
+            println!("Part {part}: {ANSI_BOLD_RED}✖{ANSI_RESET} {error}");
+            record.status = Status::Failed;
+            record.error = Some(error);
+        }
+
+        set_measurement(&mut record, &fastest.1);
+        record.variant = Some(fastest.0.to_string());
+    }
+
+    if record.status == Status::Failed {
+        return record;
+    }
+
+    if let Some(result) = &record.answer {
+        match submit_result(result, puzzle, part) {
+            Some(Ok(submission)) => println!("{}", submission.message),
+            Some(Err(e)) => eprintln!("Failed to submit result: {e}"),
+            None => {}
+        }
+    }
+
+    record
+}
+
+/// Run one implementation of a part and print its result, labeled e.g. `Part 1`.
+fn run_implementation(
+    func: PartFn,
+    parsed: &dyn Any,
+    label: &str,
+    expected: &String,
+    bench: Option<BenchConfig>,
+) -> (Option<String>, Measurement) {
+    let (result, measurement) = run_timed(func, parsed, bench, |result| {
+        print_result(result, label, "", expected)
     });
 
-    print_result(
-        &result,
-        &part_str,
-        &format_duration(&duration, samples),
-        &expected,
-    );
+    let mut duration_str = format_duration(&measurement.base_time, measurement.stats.as_ref());
+    if let Some(alloc) = measurement.alloc {
+        duration_str.push_str(&format!(" {ANSI_ITALIC}[{alloc}]{ANSI_RESET}"));
+    }
+
+    print_result(&result, label, &duration_str, expected);
+
+    (result, measurement)
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+fn format_answer(answer: &Option<String>) -> String {
+    match answer {
+        Some(answer) => format!("`{answer}`"),
+        None => "no answer".to_string(),
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Set the duration and allocation fields of a record from a measurement.
+fn set_measurement(record: &mut PartRecord, measurement: &Measurement) {
+    let Measurement {
+        base_time,
+        stats,
+        alloc,
+    } = *measurement;
+
+    record.duration_nanos = stats.map_or(base_time.as_nanos(), |s| s.mean_nanos.round() as u128);
+    record.samples = stats.map_or(1, |s| s.samples + s.outliers);
+    record.base_nanos = base_time.as_nanos();
+    record.stats = stats;
+    record.alloc = alloc;
+}
+
+/// Run a solution part. The behavior differs depending on whether a [`BenchConfig`] is passed:
+///  1. without, the function is executed once.
+///  2. with, the function is additionally benched after a warmup (by default approx. 1 second of execution time or 10 samples, whatever takes longer.)
+///
+/// Returns the result and its [`Measurement`]. Allocations are counted for the first (cold) run.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_config: Option<BenchConfig>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
+) -> (T, Measurement) {
+    let ((result, base_time), alloc) = alloc::measure(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
-    };
-    let base_time = timer.elapsed();
+        let timer = Instant::now();
+        let result = func(input);
+        (result, timer.elapsed())
+    });
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    let stats = bench_config.map(|config| bench(func, input, &base_time, &config));
 
-    (result, run.0, run.1)
+    (
+        result,
+        Measurement {
+            base_time,
+            stats,
+            alloc,
+        },
+    )
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Duration of the first (cold) run of a part, its benchmark statistics and its allocations.
+#[derive(Clone, Copy)]
+struct Measurement {
+    base_time: Duration,
+    stats: Option<Stats>,
+    alloc: Option<AllocStats>,
+}
+
+impl Measurement {
+    fn mean_nanos(&self) -> f64 {
+        self.stats
+            .map_or(self.base_time.as_nanos() as f64, |s| s.mean_nanos)
+    }
+}
+
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = config.iterations_for(base_time);
 
-    let mut timers: Vec<Duration> = vec![];
+    for _ in 0..config.warmup_for(bench_iterations) {
+        black_box(func(black_box(input)));
+    }
+
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
 
     for _ in 0..bench_iterations {
         let timer = Instant::now();
@@ -80,26 +374,20 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_samples(base_time, &timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(base_time: &Duration, stats: Option<&Stats>) -> String {
+    match stats {
+        None => format!(" ({base_time:.1?})"),
+        Some(stats) => format!(
+            " ({} @ {} samples | median {} | p95 {} | σ {} | cold {base_time:.1?})",
+            stats.format(Statistic::Mean),
+            stats.samples + stats.outliers,
+            stats.format(Statistic::Median),
+            stats.format(Statistic::P95),
+            stats.format(Statistic::StdDev),
+        ),
     }
 }
 
@@ -149,12 +437,13 @@ fn print_result<T: Display + std::cmp::PartialEq>(
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the submission log does not show that the answer is wrong.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Submission, SubmissionError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -177,11 +466,6 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    Some(submissions::submit(puzzle, part, &result.to_string()))
 }
@@ -0,0 +1,97 @@
+/// Trait-based solutions, which parse their input once and share the parsed value between both parts.
+///
+/// ```ignore
+/// advent_of_code::solution!(7, Day07);
+///
+/// pub struct Day07;
+///
+/// impl Solution for Day07 {
+///     type Parsed = Vec<u64>;
+///     type Error = ParseError;
+///     type Answer1 = u64;
+///     type Answer2 = u64;
+///
+///     fn parse(input: &str) -> Result<Self::Parsed, Self::Error> { /* ... */ }
+///     fn part_one(parsed: &Self::Parsed) -> Option<Self::Answer1> { /* ... */ }
+///     fn part_two(parsed: &Self::Parsed) -> Option<Self::Answer2> { /* ... */ }
+/// }
+/// ```
+///
+/// The runner calls [`Solution::parse`] once and times it separately from the parts.
+/// Free functions taking the input keep working, they are registered with the same type-erased signatures.
+use std::{any::Any, fmt::Display};
+
+use crate::template::params::Part;
+use crate::template::Params;
+
+pub trait Solution {
+    /// The parsed input, shared by both parts.
+    type Parsed: 'static;
+    /// Error returned if the input could not be parsed, usually a [`ParseError`](crate::template::ParseError).
+    type Error: Display;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error>;
+
+    /// Parse the input with its [`Params`]. Override this to keep parameters in the parsed value.
+    fn parse_with(input: &str, params: &Params) -> Result<Self::Parsed, Self::Error> {
+        let _ = params;
+        Self::parse(input)
+    }
+
+    fn part_one(_parsed: &Self::Parsed) -> Option<Self::Answer1> {
+        None
+    }
+
+    fn part_two(_parsed: &Self::Parsed) -> Option<Self::Answer2> {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Signature of a parse step as registered by the `solution!` macro.
+/// The parsed value is type-erased, so solutions with different `Parsed` types can be registered together.
+pub type ParseFn = fn(&str, &Params) -> Result<Box<dyn Any>, String>;
+
+/// Signature of a solution part as registered by the `solution!` macro.
+/// Parts receive the value returned by the parse step.
+pub type PartFn = fn(&dyn Any) -> Option<String>;
+
+/// The parsed value of solutions without a parse step: the input and its parameters.
+pub type RawInput = (String, Params);
+
+pub fn parse<S: Solution>(input: &str, params: &Params) -> Result<Box<dyn Any>, String> {
+    S::parse_with(input, params)
+        .map(|parsed| Box::new(parsed) as Box<dyn Any>)
+        .map_err(|e| e.to_string())
+}
+
+pub fn part_one<S: Solution>(parsed: &dyn Any) -> Option<String> {
+    S::part_one(downcast::<S::Parsed>(parsed)).map(|answer| answer.to_string())
+}
+
+pub fn part_two<S: Solution>(parsed: &dyn Any) -> Option<String> {
+    S::part_two(downcast::<S::Parsed>(parsed)).map(|answer| answer.to_string())
+}
+
+/// Call a variant of a part of a trait-based solution, which takes the parsed input like the part does.
+pub fn call_variant<S: Solution, A: Display>(
+    func: &impl Fn(&S::Parsed) -> Option<A>,
+    parsed: &dyn Any,
+) -> Option<String> {
+    func(downcast::<S::Parsed>(parsed)).map(|answer| answer.to_string())
+}
+
+/// Call a free function part with the input of a solution without a parse step.
+pub fn call_part<M>(func: &impl Part<M>, parsed: &dyn Any) -> Option<String> {
+    let (input, params) = downcast::<RawInput>(parsed);
+    func.call(input, params)
+}
+
+fn downcast<T: 'static>(parsed: &dyn Any) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("parsed value to be of the type returned by the parse step")
+}
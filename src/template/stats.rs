@@ -0,0 +1,377 @@
+/// Statistics and configuration for benchmarking solution parts.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+/// Fewest measured iterations when running for a time budget.
+const MIN_ITERATIONS: u128 = 10;
+
+/// Most measured iterations when running for a time budget.
+const MAX_ITERATIONS: u128 = 1_000_000;
+
+/// Most warmup iterations when the count is not set explicitly.
+const MAX_WARMUP: u128 = 1_000;
+
+/// Samples outside of `[Q1 - k * IQR, Q3 + k * IQR]` are rejected as outliers (Tukey's fences).
+const OUTLIER_FENCE: f64 = 1.5;
+
+/// Controls how a part is benchmarked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Approximate time spent on measured iterations, used if `iterations` is not set.
+    pub budget: Duration,
+    /// Fixed number of measured iterations.
+    pub iterations: Option<u128>,
+    /// Number of unmeasured iterations run before measuring. Defaults to a tenth of the measured iterations.
+    pub warmup: Option<u128>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            iterations: None,
+            warmup: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Number of measured iterations, based on the duration of the first (cold) run.
+    pub fn iterations_for(&self, base_time: &Duration) -> u128 {
+        self.iterations.unwrap_or_else(|| {
+            (self.budget.as_nanos() / base_time.as_nanos().max(10))
+                .clamp(MIN_ITERATIONS, MAX_ITERATIONS)
+        })
+    }
+
+    /// Number of warmup iterations for the given number of measured iterations.
+    pub fn warmup_for(&self, iterations: u128) -> u128 {
+        self.warmup
+            .unwrap_or_else(|| (iterations / 10).clamp(1, MAX_WARMUP))
+    }
+
+    /// Read the configuration from solution arguments (e.g. `--bench-time 2s --iterations 100`).
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| args.get(i + 1).ok_or(format!("missing value for {flag}.")))
+                .transpose()
+        };
+
+        let mut config = Self::default();
+
+        if let Some(budget) = value_of("--bench-time")? {
+            config.budget = parse_bench_time(budget)?;
+        }
+        if let Some(iterations) = value_of("--iterations")? {
+            config.iterations = Some(parse_count(iterations, "--iterations")?);
+        }
+        if let Some(warmup) = value_of("--warmup")? {
+            config.warmup = Some(
+                warmup
+                    .parse()
+                    .map_err(|_| "expected a number for --warmup.".to_string())?,
+            );
+        }
+
+        Ok(config)
+    }
+
+    /// Convert the configuration back to solution arguments, e.g. to mirror it to child invocations.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--bench-time".to_string(),
+            format!("{}ns", self.budget.as_nanos()),
+        ];
+        if let Some(iterations) = self.iterations {
+            args.extend(["--iterations".to_string(), iterations.to_string()]);
+        }
+        if let Some(warmup) = self.warmup {
+            args.extend(["--warmup".to_string(), warmup.to_string()]);
+        }
+        args
+    }
+}
+
+/// Parse a positive number of iterations for the given flag.
+pub fn parse_count(s: &str, flag: &str) -> Result<u128, String> {
+    match s.parse() {
+        Ok(0) | Err(_) => Err(format!("expected a positive number for {flag}.")),
+        Ok(n) => Ok(n),
+    }
+}
+
+/// Parse a benchmark time budget such as `2s`, `500ms`, `1.5` (seconds) or `100000ns`.
+pub fn parse_bench_time(s: &str) -> Result<Duration, String> {
+    let (value, factor) = if let Some(v) = s.strip_suffix("ns") {
+        (v, 1e-9)
+    } else if let Some(v) = s.strip_suffix("µs").or_else(|| s.strip_suffix("us")) {
+        (v, 1e-6)
+    } else if let Some(v) = s.strip_suffix("ms") {
+        (v, 1e-3)
+    } else {
+        (s.strip_suffix('s').unwrap_or(s), 1.0)
+    };
+
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .map(|v| Duration::from_secs_f64(v * factor))
+        .ok_or(format!(
+            "expected a duration such as `2s` or `500ms`, got `{s}`."
+        ))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A statistic of a benchmarked part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Statistic {
+    /// Duration of the first, cold run.
+    Base,
+    #[default]
+    Mean,
+    Median,
+    Min,
+    Max,
+    StdDev,
+    P95,
+}
+
+impl Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Statistic::Base => "base",
+            Statistic::Mean => "mean",
+            Statistic::Median => "median",
+            Statistic::Min => "min",
+            Statistic::Max => "max",
+            Statistic::StdDev => "stddev",
+            Statistic::P95 => "p95",
+        })
+    }
+}
+
+impl FromStr for Statistic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "base" => Ok(Statistic::Base),
+            "mean" => Ok(Statistic::Mean),
+            "median" => Ok(Statistic::Median),
+            "min" => Ok(Statistic::Min),
+            "max" => Ok(Statistic::Max),
+            "stddev" => Ok(Statistic::StdDev),
+            "p95" => Ok(Statistic::P95),
+            _ => Err(format!(
+                "unknown statistic `{s}`, expecting one of base, mean, median, min, max, stddev, p95."
+            )),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Summary of benchmark samples in nanoseconds. Outliers are rejected before computing the statistics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub base_nanos: f64,
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub stddev_nanos: f64,
+    pub p95_nanos: f64,
+    /// Number of samples the statistics are based on.
+    pub samples: u128,
+    /// Number of rejected samples.
+    pub outliers: u128,
+}
+
+impl Stats {
+    pub fn from_samples(base_time: &Duration, samples: &[Duration]) -> Self {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        let total = sorted.len();
+
+        if total >= 4 {
+            let q1 = percentile(&sorted, 25.0);
+            let q3 = percentile(&sorted, 75.0);
+            let iqr = q3 - q1;
+            let (low, high) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+            sorted.retain(|x| (low..=high).contains(x));
+        }
+
+        let count = sorted.len().max(1) as f64;
+        let mean = sorted.iter().sum::<f64>() / count;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+        Self {
+            base_nanos: base_time.as_nanos() as f64,
+            mean_nanos: mean,
+            median_nanos: percentile(&sorted, 50.0),
+            min_nanos: sorted.first().copied().unwrap_or_default(),
+            max_nanos: sorted.last().copied().unwrap_or_default(),
+            stddev_nanos: variance.sqrt(),
+            p95_nanos: percentile(&sorted, 95.0),
+            samples: sorted.len() as u128,
+            outliers: (total - sorted.len()) as u128,
+        }
+    }
+
+    pub fn get(&self, statistic: Statistic) -> f64 {
+        match statistic {
+            Statistic::Base => self.base_nanos,
+            Statistic::Mean => self.mean_nanos,
+            Statistic::Median => self.median_nanos,
+            Statistic::Min => self.min_nanos,
+            Statistic::Max => self.max_nanos,
+            Statistic::StdDev => self.stddev_nanos,
+            Statistic::P95 => self.p95_nanos,
+        }
+    }
+
+    /// Format a statistic like [`Duration`]'s debug output, e.g. `1.2ms`.
+    pub fn format(&self, statistic: Statistic) -> String {
+        format_nanos(self.get(statistic))
+    }
+}
+
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Linear interpolation between the closest ranks of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("base_nanos".into(), JsonValue::Number(value.base_nanos));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            base_nanos: number("base_nanos")?,
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            stddev_nanos: number("stddev_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_bench_time, BenchConfig, Statistic, Stats};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_samples(&Duration::from_nanos(100), &nanos(&[4, 1, 3, 2, 5]));
+        assert_eq!(stats.base_nanos, 100.0);
+        assert_eq!(stats.mean_nanos, 3.0);
+        assert_eq!(stats.median_nanos, 3.0);
+        assert_eq!(stats.min_nanos, 1.0);
+        assert_eq!(stats.max_nanos, 5.0);
+        assert!((stats.p95_nanos - 4.8).abs() < 1e-9);
+        assert_eq!(stats.stddev_nanos, 2f64.sqrt());
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(
+            &Duration::from_nanos(100),
+            &nanos(&[10, 11, 10, 12, 11, 10, 500]),
+        );
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.max_nanos, 12.0);
+    }
+
+    #[test]
+    fn parses_bench_times() {
+        assert_eq!(parse_bench_time("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_bench_time("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_bench_time("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_bench_time("100000ns"), Ok(Duration::from_micros(100)));
+        assert!(parse_bench_time("0s").is_err());
+        assert!(parse_bench_time("soon").is_err());
+    }
+
+    #[test]
+    fn roundtrips_bench_config_args() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(250),
+            iterations: Some(42),
+            warmup: Some(3),
+        };
+        assert_eq!(BenchConfig::from_args(&config.to_args()), Ok(config));
+    }
+
+    #[test]
+    fn derives_iterations_from_budget() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations_for(&Duration::from_millis(1)), 1000);
+        assert_eq!(config.iterations_for(&Duration::from_secs(5)), 10);
+        assert_eq!(config.warmup_for(1000), 100);
+    }
+
+    #[test]
+    fn parses_statistics() {
+        assert_eq!("p95".parse::<Statistic>(), Ok(Statistic::P95));
+        assert!("average".parse::<Statistic>().is_err());
+    }
+}
@@ -0,0 +1,394 @@
+/// Local log of submitted answers, used to avoid submitting answers that are known to be wrong.
+///
+/// Every submission is appended to `data/submissions.jsonl` together with the verdict of the website.
+/// Before submitting, answers that were already rejected or that contradict a "too high" / "too low" hint
+/// are refused. Correct answers are written to `data/outputs`.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{AocClient, AocClientError, Submission, Verdict};
+use crate::template::{year_data_dir, Day, Puzzle, Year};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.jsonl";
+
+fn submissions_file_path(year: Year) -> PathBuf {
+    year_data_dir(year).join(SUBMISSIONS_FILE_NAME)
+}
+
+/// A submitted answer and the verdict of the website.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubmissionEntry {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the UNIX epoch.
+    pub timestamp: u64,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Refusal {
+    /// The part has already been solved with the given answer.
+    AlreadySolved(String),
+    /// The answer has already been submitted and was wrong.
+    KnownWrong,
+    /// The answer is not lower than an answer that was too high.
+    NotBelow(String),
+    /// The answer is not higher than an answer that was too low.
+    NotAbove(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "the part has already been solved with \"{answer}\".")
+            }
+            Refusal::KnownWrong => {
+                write!(f, "the answer has already been submitted and was wrong.")
+            }
+            Refusal::NotBelow(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than \"{bound}\", which was too high."
+                )
+            }
+            Refusal::NotAbove(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than \"{bound}\", which was too low."
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmissionError {
+    Refused(Refusal),
+    Client(AocClientError),
+    IO(io::Error),
+}
+
+impl Display for SubmissionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionError::Refused(refusal) => write!(f, "refusing to submit, {refusal}"),
+            SubmissionError::Client(e) => write!(f, "{e}"),
+            SubmissionError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AocClientError> for SubmissionError {
+    fn from(e: AocClientError) -> Self {
+        SubmissionError::Client(e)
+    }
+}
+
+impl From<io::Error> for SubmissionError {
+    fn from(e: io::Error) -> Self {
+        SubmissionError::IO(e)
+    }
+}
+
+/// All submissions of a year, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct SubmissionLog {
+    pub entries: Vec<SubmissionEntry>,
+}
+
+impl SubmissionLog {
+    /// Read the log file. If not present, returns an empty log. Malformed entries are skipped.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(submissions_file_path(year))
+            .map(|content| Self::from_lines(&content))
+            .unwrap_or_default()
+    }
+
+    fn from_lines(content: &str) -> Self {
+        let entries = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                JsonValue::from_str(line)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| SubmissionEntry::try_from(&json))
+                    .map_err(|e| eprintln!("Skipping malformed submission entry: {e}"))
+                    .ok()
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Append an entry to the log file.
+    pub fn append(year: Year, entry: &SubmissionEntry) -> Result<(), io::Error> {
+        let json = JsonValue::from(entry)
+            .stringify()
+            .map_err(io::Error::other)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(submissions_file_path(year))?;
+        writeln!(file, "{json}")
+    }
+
+    /// Check whether an answer may be submitted, based on earlier submissions of the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let entries = || {
+            self.entries
+                .iter()
+                .filter(move |e| e.day == day && e.part == part)
+        };
+
+        if let Some(entry) = entries().find(|e| e.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(entry.answer.clone()));
+        }
+
+        let is_wrong = |e: &SubmissionEntry| {
+            matches!(
+                e.verdict,
+                Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+            )
+        };
+
+        if entries().any(|e| is_wrong(e) && e.answer == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        // NOTE: bounds only apply to numeric answers.
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            entries()
+                .filter(move |e| e.verdict == verdict)
+                .filter_map(|e| Some((e.answer.trim().parse::<i128>().ok()?, &e.answer)))
+        };
+
+        if let Some((_, high)) = bound(Verdict::TooHigh)
+            .min_by_key(|(v, _)| *v)
+            .filter(|(v, _)| value >= *v)
+        {
+            return Err(Refusal::NotBelow(high.clone()));
+        }
+
+        if let Some((_, low)) = bound(Verdict::TooLow)
+            .max_by_key(|(v, _)| *v)
+            .filter(|(v, _)| value <= *v)
+        {
+            return Err(Refusal::NotAbove(low.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Submit an answer, unless the submission log shows that it is wrong.
+/// Logs the verdict and stores correct answers in `data/outputs`.
+pub fn submit(puzzle: Puzzle, part: u8, answer: &str) -> Result<Submission, SubmissionError> {
+    SubmissionLog::read_from_file(puzzle.year)
+        .check(puzzle.day, part, answer)
+        .map_err(SubmissionError::Refused)?;
+
+    let submission = AocClient::from_env()?.submit(puzzle, part, answer)?;
+
+    let entry = SubmissionEntry {
+        day: puzzle.day,
+        part,
+        answer: answer.to_string(),
+        verdict: submission.verdict,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs()),
+    };
+
+    if let Err(e) = SubmissionLog::append(puzzle.year, &entry) {
+        eprintln!("Failed to log submission: {e}");
+    }
+
+    if submission.verdict == Verdict::Correct {
+        store_expected_answer(puzzle, part, answer)?;
+    }
+
+    Ok(submission)
+}
+
+/// Write a correct answer to the line of the part in `data/outputs`.
+fn store_expected_answer(puzzle: Puzzle, part: u8, answer: &str) -> Result<(), io::Error> {
+    let path = puzzle.data_path("outputs", "txt");
+    let content = fs::read_to_string(&path).unwrap_or_default();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, with_answer(&content, part, answer))
+}
+
+fn with_answer(content: &str, part: u8, answer: &str) -> String {
+    let mut lines: Vec<&str> = if content.is_empty() {
+        vec![]
+    } else {
+        content.split('\n').collect()
+    };
+
+    let index = part as usize - 1;
+    if lines.len() <= index {
+        lines.resize(index + 1, "");
+    }
+    lines[index] = answer;
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SubmissionEntry> for JsonValue {
+    fn from(value: &SubmissionEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for SubmissionEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        Ok(SubmissionEntry {
+            day: Day::from_str(string("day")?)
+                .map_err(|_| "Expected submission.day to be a Day struct.")?,
+            part: number("part")? as u8,
+            answer: string("answer")?.clone(),
+            verdict: string("verdict")?.parse()?,
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{with_answer, Refusal, SubmissionEntry, SubmissionLog};
+    use crate::{day, template::aoc_client::Verdict};
+
+    fn entry(part: u8, answer: &str, verdict: Verdict) -> SubmissionEntry {
+        SubmissionEntry {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: 0,
+        }
+    }
+
+    fn get_mock_log() -> SubmissionLog {
+        SubmissionLog {
+            entries: vec![
+                entry(1, "100", Verdict::TooHigh),
+                entry(1, "120", Verdict::TooHigh),
+                entry(1, "10", Verdict::TooLow),
+                entry(1, "50", Verdict::Incorrect),
+                entry(1, "60", Verdict::TooRecent),
+                entry(2, "abc", Verdict::Correct),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let log = get_mock_log();
+        assert_eq!(log.check(day!(1), 1, "50"), Err(Refusal::KnownWrong));
+        assert_eq!(log.check(day!(1), 1, "60"), Ok(()));
+        assert_eq!(log.check(day!(2), 1, "50"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_of_bounds() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(day!(1), 1, "101"),
+            Err(Refusal::NotBelow("100".into()))
+        );
+        assert_eq!(
+            log.check(day!(1), 1, "110"),
+            Err(Refusal::NotBelow("100".into()))
+        );
+        assert_eq!(
+            log.check(day!(1), 1, "5"),
+            Err(Refusal::NotAbove("10".into()))
+        );
+        assert_eq!(log.check(day!(1), 1, "99"), Ok(()));
+        assert_eq!(log.check(day!(1), 1, "not a number"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        assert_eq!(
+            get_mock_log().check(day!(1), 2, "def"),
+            Err(Refusal::AlreadySolved("abc".into()))
+        );
+    }
+
+    #[test]
+    fn reads_logged_entries() {
+        let lines = get_mock_log()
+            .entries
+            .iter()
+            .map(|e| JsonValue::from(e).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let log = SubmissionLog::from_lines(&lines);
+        assert_eq!(log.entries, get_mock_log().entries);
+    }
+
+    #[test]
+    fn stores_answers_by_part() {
+        assert_eq!(with_answer("", 1, "42"), "42");
+        assert_eq!(with_answer("", 2, "42"), "\n42");
+        assert_eq!(with_answer("1\n", 2, "42"), "1\n42");
+        assert_eq!(with_answer("1\n2", 1, "42"), "42\n2");
+    }
+}
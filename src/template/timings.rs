@@ -1,17 +1,159 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::record::PartRecord;
+use crate::template::stats::{parse_bench_time, Statistic, Stats};
+use crate::template::{year_data_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Timings are stored next to the year's data, e.g. `data/timings.json` or `data/2023/timings.json`.
+fn timings_file_path(year: Year) -> PathBuf {
+    year_data_dir(year).join(TIMINGS_FILE_NAME)
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Duration of the parse step, for solutions that have one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Sum of the parse step and both parts.
     pub total_nanos: f64,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Name of the fastest implementation of a part with variants, whose timing is stored.
+    pub part_1_variant: Option<String>,
+    pub part_2_variant: Option<String>,
+    /// Allocations of the parts, if they were counted.
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+}
+
+impl Timing {
+    /// Collect the timings of a day from the records of its parts.
+    /// Parts without an answer are not timed. The parse step is shared by the parts and counted once.
+    pub fn from_records(day: Day, records: &[PartRecord]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_variant: None,
+            part_2_variant: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+        };
+
+        if let Some(record) = records.iter().find(|r| r.parse_nanos.is_some()) {
+            let parse_nanos = record.parse_nanos.unwrap_or_default();
+            timing.parse = Some(format_duration(parse_nanos));
+            timing.parse_stats = record.parse_stats;
+            timing.total_nanos += parse_nanos as f64;
+        }
+
+        for record in records.iter().filter(|r| r.answer.is_some()) {
+            let formatted = Some(format_duration(record.duration_nanos));
+            let variant = record.variant.clone();
+
+            match record.part {
+                1 => {
+                    (
+                        timing.part_1,
+                        timing.part_1_stats,
+                        timing.part_1_variant,
+                        timing.part_1_alloc,
+                    ) = (formatted, record.stats, variant, record.alloc)
+                }
+                2 => {
+                    (
+                        timing.part_2,
+                        timing.part_2_stats,
+                        timing.part_2_variant,
+                        timing.part_2_alloc,
+                    ) = (formatted, record.stats, variant, record.alloc)
+                }
+                _ => continue,
+            }
+
+            timing.total_nanos += record.duration_nanos as f64;
+        }
+
+        timing
+    }
+
+    /// Format a statistic of a part. The mean falls back to the stored timing, which older timing files only contain.
+    pub fn format_part(&self, part: u8, statistic: Statistic) -> Option<String> {
+        let (formatted, stats) = match part {
+            1 => (&self.part_1, &self.part_1_stats),
+            _ => (&self.part_2, &self.part_2_stats),
+        };
+        format_phase(formatted, stats, statistic)
+    }
+
+    /// Format a statistic of the parse step, like [`Timing::format_part`].
+    pub fn format_parse(&self, statistic: Statistic) -> Option<String> {
+        format_phase(&self.parse, &self.parse_stats, statistic)
+    }
+
+    /// A statistic of a part in nanoseconds. Like [`Timing::format_part`], the mean falls back to the stored timing.
+    pub fn part_nanos(&self, part: u8, statistic: Statistic) -> Option<f64> {
+        let (formatted, stats) = match part {
+            1 => (&self.part_1, &self.part_1_stats),
+            _ => (&self.part_2, &self.part_2_stats),
+        };
+
+        match (statistic, stats) {
+            (_, Some(stats)) => Some(stats.get(statistic)),
+            (Statistic::Mean, None) => formatted
+                .as_deref()
+                .and_then(|x| parse_bench_time(x).ok())
+                .map(|x| x.as_nanos() as f64),
+            (_, None) => None,
+        }
+    }
+
+    /// Allocations of both parts, if they were counted.
+    pub fn alloc(&self) -> Option<AllocStats> {
+        AllocStats::combine([self.part_1_alloc, self.part_2_alloc].into_iter().flatten())
+    }
+
+    /// Sum of a statistic over the parse step and both parts in nanoseconds.
+    pub fn total_of(&self, statistic: Statistic) -> f64 {
+        if statistic == Statistic::Mean {
+            return self.total_nanos;
+        }
+
+        [&self.parse_stats, &self.part_1_stats, &self.part_2_stats]
+            .into_iter()
+            .flatten()
+            .map(|stats| stats.get(statistic))
+            .sum()
+    }
+}
+
+fn format_duration(nanos: u128) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+fn format_phase(
+    formatted: &Option<String>,
+    stats: &Option<Stats>,
+    statistic: Statistic,
+) -> Option<String> {
+    match (statistic, stats) {
+        (_, Some(stats)) => Some(stats.format(statistic)),
+        (Statistic::Mean, None) => formatted.clone(),
+        (_, None) => None,
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -23,15 +165,15 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(timings_file_path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(timings_file_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,13 +193,18 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.total_millis_of(Statistic::Mean)
+    }
+
+    /// Sum up a statistic of all timings as millis.
+    pub fn total_millis_of(&self, statistic: Statistic) -> f64 {
+        self.data.iter().map(|x| x.total_of(statistic)).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -114,6 +261,10 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +284,34 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            if let Some(stats) = stats {
+                map.insert(key.into(), JsonValue::from(stats));
+            }
+        }
+
+        for (key, variant) in [
+            ("part_1_variant", &value.part_1_variant),
+            ("part_2_variant", &value.part_2_variant),
+        ] {
+            if let Some(variant) = variant {
+                map.insert(key.into(), JsonValue::String(variant.clone()));
+            }
+        }
+
+        for (key, alloc) in [
+            ("part_1_alloc", &value.part_1_alloc),
+            ("part_2_alloc", &value.part_2_alloc),
+        ] {
+            if let Some(alloc) = alloc {
+                map.insert(key.into(), JsonValue::from(alloc));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +345,32 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: the parse step, statistics, variants and allocations are optional, timing files written by older versions do not contain them.
+        let optional_string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => Stats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        let alloc = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => AllocStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
+            parse: optional_string("parse"),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse_stats: stats("parse_stats")?,
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
+            part_1_variant: optional_string("part_1_variant"),
+            part_2_variant: optional_string("part_2_variant"),
+            part_1_alloc: alloc("part_1_alloc")?,
+            part_2_alloc: alloc("part_2_alloc")?,
         })
     }
 }
@@ -188,28 +388,55 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{stats::Statistic, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +450,33 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "base_nanos": 2000000, "mean_nanos": 1000000, "median_nanos": 900000, "min_nanos": 800000, "max_nanos": 1200000, "stddev_nanos": 100000, "p95_nanos": 1100000, "samples": 98, "outliers": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_stats.unwrap().samples, 98);
+            assert_eq!(timing.part_2_stats, None);
+            assert_eq!(
+                timing.format_part(1, Statistic::Median),
+                Some("900.0µs".to_string())
+            );
+            assert_eq!(timing.total_of(Statistic::Min), 800_000_f64);
+        }
+
+        #[test]
+        fn handles_json_timings_with_parse_step() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.0ms", "part_1": "1.0ms", "part_2": "1.0ms", "total_nanos": 4000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.format_parse(Statistic::Mean),
+                Some("2.0ms".to_string())
+            );
+            assert_eq!(timing.format_parse(Statistic::Median), None);
+            assert_eq!(timing.total_of(Statistic::Mean), 4_000_000_f64);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -279,13 +533,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +555,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +577,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,9 +609,17 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +637,17 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_variant: None,
+                    part_2_variant: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,234 @@
+/// Watch mode of `cargo solve`: runs a solution again whenever its source or data files change.
+///
+/// Files are polled for changes, which works the same on every platform and for files that are created later on,
+/// e.g. an example that is added while working on a puzzle.
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{stdout, Write},
+    path::PathBuf,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::record::PartRecord;
+use crate::template::run_multi::child_commands;
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Data folders with files of the day that are watched.
+const DATA_FOLDERS: [&str; 3] = ["inputs", "examples", "outputs"];
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of the watched files. Files that disappeared are missing from the snapshot.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Run a solution, and again whenever its files change. Runs the tests of the day after the solution if `tests` is set.
+/// Never returns, stop watching with Ctrl+C.
+pub fn run(puzzle: Puzzle, release: bool, tests: bool) -> ! {
+    let mut previous: Option<Vec<PartRecord>> = None;
+    let mut snapshot = take_snapshot(puzzle);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!(
+            "{ANSI_BOLD}Day {}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, press Ctrl+C to stop){ANSI_RESET}",
+            puzzle.day
+        );
+        println!("------");
+
+        // NOTE: always run the solution binary, solutions compiled into this binary would not pick up changes.
+        let records = child_commands::run_solution(puzzle, None, release, None)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        if let Some(previous) = &previous {
+            let changes = answer_changes(previous, &records);
+            if !changes.is_empty() {
+                println!("---");
+                changes.iter().for_each(|line| println!("{line}"));
+            }
+        }
+
+        if tests {
+            println!("---");
+            run_tests(puzzle);
+        }
+
+        // a run without records failed to build or crashed, compare the next run with the last one that did not.
+        if !records.is_empty() {
+            previous = Some(records);
+        }
+
+        let _ = stdout().flush();
+        snapshot = wait_for_change(puzzle, snapshot);
+    }
+}
+
+fn run_tests(puzzle: Puzzle) {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.bin_name()])
+        .status();
+
+    if let Err(e) = status {
+        eprintln!("Failed to run tests: {e}");
+    }
+}
+
+/// Wait until a watched file changes and no further changes follow within a poll interval,
+/// as editors often write a file in multiple steps. Returns the snapshot after the change.
+fn wait_for_change(puzzle: Puzzle, snapshot: Snapshot) -> Snapshot {
+    let mut current = snapshot.clone();
+
+    while current == snapshot {
+        thread::sleep(POLL_INTERVAL);
+        current = take_snapshot(puzzle);
+    }
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let next = take_snapshot(puzzle);
+        if next == current {
+            return current;
+        }
+        current = next;
+    }
+}
+
+fn take_snapshot(puzzle: Puzzle) -> Snapshot {
+    watched_files(puzzle)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// The solution file, the library and the data files of the day, e.g. `data/examples/01-2.txt` or `data/inputs/01.params`.
+fn watched_files(puzzle: Puzzle) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(puzzle.bin_path()),
+        PathBuf::from("src/lib.rs"),
+    ];
+
+    for folder in DATA_FOLDERS {
+        let Ok(entries) = fs::read_dir(puzzle.data_path_with_name(folder, "")) else {
+            continue;
+        };
+
+        files.extend(
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| is_day_file(puzzle, name))
+                }),
+        );
+    }
+
+    files
+}
+
+fn is_day_file(puzzle: Puzzle, file_name: &str) -> bool {
+    file_name
+        .strip_prefix(&puzzle.day.to_string())
+        .is_some_and(|rest| rest.starts_with(['.', '-']))
+}
+
+/// Describe the answers of a run relative to the previous run, one line per part.
+fn answer_changes(previous: &[PartRecord], current: &[PartRecord]) -> Vec<String> {
+    let format_answer = |answer: Option<&str>| match answer {
+        None => "✖".to_string(),
+        Some(answer) if answer.contains('\n') => "▼".to_string(),
+        Some(answer) => answer.to_string(),
+    };
+
+    current
+        .iter()
+        .map(|record| {
+            let answer = record.answer.as_deref();
+            let before = previous
+                .iter()
+                .find(|r| r.part == record.part)
+                .and_then(|r| r.answer.as_deref());
+
+            let change = if answer == before {
+                "unchanged".to_string()
+            } else {
+                format!("was {}", format_answer(before))
+            };
+
+            format!(
+                "Part {}: {} {ANSI_ITALIC}({change}){ANSI_RESET}",
+                record.part,
+                format_answer(answer)
+            )
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_changes, is_day_file};
+    use crate::{
+        day,
+        template::{
+            record::{PartRecord, Status},
+            Puzzle, ANSI_ITALIC, ANSI_RESET,
+        },
+        year,
+    };
+
+    fn record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord {
+            puzzle: Puzzle::new(year!(2024), day!(1)),
+            part,
+            answer: answer.map(|x| x.to_string()),
+            expected: None,
+            status: Status::evaluate(answer, None),
+            duration_nanos: 0,
+            samples: 1,
+            base_nanos: 0,
+            stats: None,
+            parse_nanos: None,
+            parse_stats: None,
+            variant: None,
+            alloc: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn matches_day_files() {
+        let puzzle = Puzzle::new(year!(2024), day!(1));
+
+        assert!(is_day_file(puzzle, "01.txt"));
+        assert!(is_day_file(puzzle, "01-2.txt"));
+        assert!(is_day_file(puzzle, "01.params"));
+        assert!(!is_day_file(puzzle, "011.txt"));
+        assert!(!is_day_file(puzzle, "10.txt"));
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        let previous = vec![record(1, Some("11")), record(2, None)];
+        let current = vec![record(1, Some("11")), record(2, Some("31"))];
+
+        assert_eq!(
+            answer_changes(&previous, &current),
+            vec![
+                format!("Part 1: 11 {ANSI_ITALIC}(unchanged){ANSI_RESET}"),
+                format!("Part 2: 31 {ANSI_ITALIC}(was ✖){ANSI_RESET}"),
+            ]
+        );
+    }
+}